@@ -0,0 +1,67 @@
+//! src/activity.rs
+//!
+//! LRB/EVSIDS-achtige activiteitsscores per knoop: een vector `act` die
+//! wordt opgehoogd telkens een knoop deelneemt aan een *verbeterende*
+//! add/remove move, met exponentiële bumping (`inc` wordt elke iteratie
+//! gedeeld door een decay-factor). De decay-factor zelf wordt geannealed:
+//! hij start laag (snel vergeten, sterke exploratie) en stijgt richting
+//! het einde van de zoektocht (trage vergetelheid, favoriet voor knopen
+//! met aanhoudende historische payoff).
+
+use crate::params::Params;
+
+/// Houdt de activiteitsvector en de annealed bumping-toestand bij.
+#[derive(Clone, Debug)]
+pub struct Activity {
+    act: Vec<f64>,
+    inc: f64,
+    decay: f64,
+}
+
+impl Activity {
+    /// Creëert een nieuwe, lege activiteitstracker voor `n` knopen.
+    pub fn new(n: usize, p: &Params) -> Self {
+        Self {
+            act: vec![0.0; n],
+            inc: 1.0,
+            decay: p.activity_decay_start,
+        }
+    }
+
+    /// Geeft de huidige activiteitsscore van knoop `v` terug.
+    #[inline]
+    pub fn get(&self, v: usize) -> f64 {
+        self.act[v]
+    }
+
+    /// Beloont knoop `v` voor deelname aan een verbeterende move.
+    #[inline]
+    pub fn bump(&mut self, v: usize) {
+        self.act[v] += self.inc;
+    }
+
+    /// Werkt de annealed decay-factor bij op basis van de voortgang
+    /// `progress` in `[0, 1]` doorheen de zoektocht, en schaalt `inc` op
+    /// (exponentiële bumping). Herschaalt `act` en `inc` als `inc` dreigt
+    /// te overflowen.
+    pub fn anneal(&mut self, progress: f64, p: &Params) {
+        let progress = progress.clamp(0.0, 1.0);
+        self.decay = p.activity_decay_start + (p.activity_decay_end - p.activity_decay_start) * progress;
+        self.inc /= self.decay.max(1e-9);
+
+        if self.inc > 1e100 {
+            for a in self.act.iter_mut() {
+                *a *= 1e-100;
+            }
+            self.inc *= 1e-100;
+        }
+    }
+
+    /// Zet alle activiteitsscores en de bumping-toestand terug naar nul,
+    /// bv. na een volledige restart.
+    pub fn reset(&mut self, p: &Params) {
+        self.act.fill(0.0);
+        self.inc = 1.0;
+        self.decay = p.activity_decay_start;
+    }
+}
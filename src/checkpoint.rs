@@ -0,0 +1,115 @@
+// src/checkpoint.rs
+//! Checkpoint-and-resume voor lange multi-run sweeps (`solve_k_py` /
+//! `solve_max_py`): na elke voltooide run wordt de huidige beste oplossing
+//! atomisch weggeschreven naar een sidecar-bestand, zodat een onderbroken
+//! sweep kan hervatten vanaf de laatst voltooide run-index in plaats van
+//! van voren af aan te beginnen.
+
+use crate::graph::Graph;
+use crate::solution::Solution;
+use bitvec::prelude::*;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// De staat die tussen runs bewaard wordt: de knoopverzameling van de beste
+/// tot dusver gevonden oplossing, plus de run-index waarbij de sweep moet
+/// hervatten.
+pub struct Checkpoint {
+    pub members: Vec<usize>,
+    pub next_run_index: usize,
+}
+
+impl Checkpoint {
+    /// Seriazeert naar een simpel regel-gebaseerd tekstformaat: regel 1 is
+    /// `next_run_index`, regel 2 is de spatiegescheiden knoopverzameling.
+    fn serialize(&self) -> String {
+        let members: Vec<String> = self.members.iter().map(|v| v.to_string()).collect();
+        format!("{}\n{}\n", self.next_run_index, members.join(" "))
+    }
+
+    fn deserialize(text: &str) -> io::Result<Self> {
+        let mut lines = text.lines();
+        let next_run_index: usize = lines
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "checkpoint mist run-index regel"))?
+            .trim()
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("ongeldige run-index: {e}")))?;
+        let members = lines
+            .next()
+            .unwrap_or("")
+            .split_whitespace()
+            .map(|tok| {
+                tok.parse::<usize>()
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("ongeldige knoopindex: {e}")))
+            })
+            .collect::<io::Result<Vec<usize>>>()?;
+        Ok(Checkpoint { members, next_run_index })
+    }
+
+    /// Bouwt de bewaarde knoopverzameling terug op tot een `Solution<'g>`
+    /// op basis van `graph`.
+    pub fn to_solution<'g>(&self, graph: &'g Graph) -> Solution<'g> {
+        let mut bitset = bitvec![0; graph.n()];
+        for &v in &self.members {
+            if v < graph.n() {
+                bitset.set(v, true);
+            }
+        }
+        Solution::rebuild_from_bitset(graph, &bitset)
+    }
+
+    /// Laadt een checkpoint van `path`, of geeft `None` als het bestand niet
+    /// bestaat.
+    pub fn load(path: &str) -> io::Result<Option<Self>> {
+        if !Path::new(path).exists() {
+            return Ok(None);
+        }
+        let mut text = String::new();
+        File::open(path)?.read_to_string(&mut text)?;
+        Checkpoint::deserialize(&text).map(Some)
+    }
+
+    /// Schrijft deze checkpoint atomisch weg naar `path`: eerst naar een
+    /// tijdelijk bestand in dezelfde map met een restrictieve mode (enkel
+    /// door de eigenaar leesbaar/schrijfbaar), daarna een `rename()` over
+    /// het uiteindelijke pad, zodat lezers nooit een half geschreven of
+    /// wereld-leesbaar bestand kunnen waarnemen. Een transiënte
+    /// bestandssysteemfout op het openen of hernoemen krijgt één extra
+    /// herkansing voordat de fout wordt teruggegeven.
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let tmp_path = format!("{path}.tmp");
+        let data = self.serialize();
+
+        let mut last_err = None;
+        for attempt in 0..2 {
+            match Self::write_and_rename(&tmp_path, path, &data) {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    last_err = Some(e);
+                    if attempt == 0 {
+                        continue;
+                    }
+                }
+            }
+        }
+        Err(last_err.unwrap())
+    }
+
+    fn write_and_rename(tmp_path: &str, final_path: &str, data: &str) -> io::Result<()> {
+        {
+            let mut options = OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            let mut file = options.open(tmp_path)?;
+            file.write_all(data.as_bytes())?;
+            file.sync_all()?;
+        }
+        fs::rename(tmp_path, final_path)
+    }
+}
@@ -0,0 +1,126 @@
+//! src/exact.rs
+//!
+//! Exacte alpha-beta branch-and-bound solver voor γ-quasi-cliques, bedoeld
+//! voor kleine instanties of een kleine resterende zoekruimte (zie
+//! `Params::exact_max_n`). Complementeert de heuristische `solve_maxk` uit
+//! `maxk.rs`: beslist voor een doelgrootte `k` of er een γ-feasible subset
+//! bestaat, door te branchen op include/exclude van de volgende knoop en
+//! takken af te snijden zodra zelfs de optimistische voltooiing de
+//! `needed_edges`-drempel niet kan halen.
+
+use crate::{graph::Graph, params::Params, solution::Solution};
+use std::time::Instant;
+
+/// Zoekt exact naar een γ-feasible subset van grootte `k`.
+/// Geeft `(Some(oplossing), false)` terug zodra er een feasible subset is
+/// gevonden, `(None, false)` als bewezen is dat er geen bestaat, en
+/// `(None, true)` bij een timeout — in dat laatste geval moet de aanroeper
+/// terugvallen op de heuristische oplosser, want de afwezigheid van een
+/// resultaat is dan niet bewezen.
+pub fn solve_exact_k<'g>(
+    graph: &'g Graph,
+    k: usize,
+    p: &Params,
+    start_time: &Instant,
+) -> (Option<Solution<'g>>, bool) {
+    let n = graph.n();
+    if k == 0 || k > n {
+        return (None, false);
+    }
+
+    let max_possible_edges = k * (k.saturating_sub(1)) / 2;
+    let needed_edges = (p.gamma_target * max_possible_edges as f64).ceil() as usize;
+    if max_possible_edges < needed_edges {
+        return (None, false);
+    }
+
+    // De kandidaten worden niet vooraf één keer gesorteerd: `branch` kiest
+    // bij elke stap opnieuw welke knoop als volgende aan de beurt komt, op
+    // basis van haar graad *binnen de huidige partiële verzameling* (zie
+    // hieronder), wat evolueert naarmate er knopen worden toegevoegd.
+    let mut candidates: Vec<usize> = (0..n).collect();
+
+    let mut partial = Solution::new(graph);
+    let mut timed_out = false;
+    let found = branch(graph, &mut partial, &mut candidates, k, needed_edges, p, start_time, &mut timed_out);
+    (found, timed_out)
+}
+
+/// Eén branch-and-bound stap: kiest de meest veelbelovende knoop uit
+/// `candidates` en beslist include/exclude voor die knoop.
+#[allow(clippy::too_many_arguments)]
+fn branch<'g>(
+    graph: &'g Graph,
+    partial: &mut Solution<'g>,
+    candidates: &mut Vec<usize>,
+    k: usize,
+    needed_edges: usize,
+    p: &Params,
+    start_time: &Instant,
+    timed_out: &mut bool,
+) -> Option<Solution<'g>> {
+    if *timed_out {
+        return None;
+    }
+    if p.max_time_seconds > 0.0 && start_time.elapsed().as_secs_f64() >= p.max_time_seconds {
+        *timed_out = true;
+        return None;
+    }
+
+    if partial.size() == k {
+        return if partial.edges() >= needed_edges {
+            Some(partial.clone())
+        } else {
+            None
+        };
+    }
+
+    let remaining_slots = k - partial.size();
+    if candidates.len() < remaining_slots {
+        return None; // Te weinig kandidaten over om k te bereiken.
+    }
+
+    // Bovengrens op het nog haalbare aantal randen: optimistische voltooiing
+    // met de hoogste resterende bijdragen, analoog aan de prefix-bound in
+    // `maxk.rs`, maar beperkt tot de nog in aanmerking komende knopen. Een
+    // nieuwe knoop `v` brengt `deg_in_s(v)` randen naar de partiële
+    // verzameling mee, plus hoogstens `degree(v) - deg_in_s(v)` randen naar
+    // andere nieuwe knopen (die elk dubbel geteld worden), dus de som van
+    // `degree(v) + deg_in_s(v)`, gehalveerd, begrenst de extra randen.
+    let mut remaining_degrees: Vec<usize> = candidates
+        .iter()
+        .map(|&v| graph.degree(v) + partial.deg_in_s(v))
+        .collect();
+    remaining_degrees.sort_unstable_by(|a, b| b.cmp(a));
+    let optimistic_additional: usize = remaining_degrees.iter().take(remaining_slots).sum();
+    if partial.edges() + optimistic_additional / 2 < needed_edges {
+        return None; // Prune: zelfs het beste geval haalt de drempel niet.
+    }
+
+    // Best-first: kies de kandidaat met de meeste connecties naar de
+    // *huidige partiële verzameling* (niet de statische globale graad), zodat
+    // veelbelovende takken — en dus strakke bounds — als eerste onderzocht
+    // worden. Bij gelijke `deg_in_s` breekt de globale graad de gelijkstand.
+    // Bij een lege partiële verzameling (de wortel) valt dit vanzelf terug op
+    // pure globale graad, zoals de oorspronkelijke statische ordening.
+    let best_pos = candidates
+        .iter()
+        .enumerate()
+        .max_by_key(|&(_, &v)| (partial.deg_in_s(v), graph.degree(v)))
+        .map(|(i, _)| i)
+        .unwrap();
+    let v = candidates.remove(best_pos);
+
+    // Probeer eerst INCLUDE — de net gekozen veelbelovende tak — dan EXCLUDE.
+    partial.add(v);
+    let found = branch(graph, partial, candidates, k, needed_edges, p, start_time, timed_out);
+    partial.remove(v);
+    if found.is_some() || *timed_out {
+        candidates.push(v);
+        return found;
+    }
+
+    let found = branch(graph, partial, candidates, k, needed_edges, p, start_time, timed_out);
+    candidates.push(v);
+    found
+}
@@ -6,8 +6,40 @@
 //! TSQC- en MCTS-algoritmes. Ondersteunt het parsen van het DIMACS *.clq-formaat.
 
 use bitvec::prelude::*;
+use std::collections::VecDeque;
 use std::io::{self, BufRead, Read};
 
+// --- NIEUW ---
+/// Snijdt ASCII-witruimte (spaties, tabs, `\r`) van beide uiteinden van een
+/// byte-slice, analoog aan `str::trim` maar zonder UTF-8-validatie — de
+/// memory-mapped parser werkt rechtstreeks op ruwe bytes.
+fn trim_ascii(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|&b| !b.is_ascii_whitespace()).unwrap_or(bytes.len());
+    let end = bytes.iter().rposition(|&b| !b.is_ascii_whitespace()).map_or(start, |i| i + 1);
+    &bytes[start..end]
+}
+
+/// Slaat bytes over tot aan het eerstvolgende cijfer (of het einde van de
+/// regel), voor het overslaan van scheidingstekens zoals `"p edge "`.
+fn skip_non_digits(bytes: &[u8], mut pos: usize) -> usize {
+    while pos < bytes.len() && !bytes[pos].is_ascii_digit() {
+        pos += 1;
+    }
+    pos
+}
+
+/// Parset een niet-negatief geheel getal vanaf `pos`, ervan uitgaande dat
+/// daar een cijfer begint. Geeft `(waarde, positie_na_het_getal)` terug.
+fn parse_uint_at(bytes: &[u8], mut pos: usize) -> (usize, usize) {
+    let mut value: usize = 0;
+    while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+        value = value * 10 + (bytes[pos] - b'0') as usize;
+        pos += 1;
+    }
+    (value, pos)
+}
+// --- EINDE NIEUW ---
+
 /// Een ongerichte graaf, opgeslagen als een row-major adjacency matrix.
 #[derive(Clone, Debug)]
 pub struct Graph {
@@ -87,6 +119,73 @@ impl Graph {
         Ok(Self::from_edge_list(n, &edges))
     }
 
+    // --- NIEUW ---
+    /// Parset het DIMACS *.clq formaat rechtstreeks vanuit een memory-mapped
+    /// bestand, voor de grote ijle grafen die bij MQCP-benchmarks gebruikt
+    /// worden: geen regel-voor-regel `String`-allocatie, enkel handmatig
+    /// geheeltallen scannen over de gemapte `&[u8]`. Een memory-map is enkel
+    /// geldig voor reguliere bestanden; valt `path` op een pipe/FIFO of
+    /// ander niet-regulier bestand (waarvoor `mmap` zou falen), dan wordt
+    /// automatisch teruggevallen op `parse_dimacs` met een gebufferde reader.
+    pub fn parse_dimacs_mmap<P: AsRef<std::path::Path>>(path: P) -> io::Result<Self> {
+        let file = std::fs::File::open(path.as_ref())?;
+
+        if !file.metadata()?.is_file() {
+            return Self::parse_dimacs(io::BufReader::new(file));
+        }
+
+        let mmap = match unsafe { memmap2::Mmap::map(&file) } {
+            Ok(m) => m,
+            Err(_) => return Self::parse_dimacs(io::BufReader::new(file)),
+        };
+
+        Self::parse_dimacs_bytes(&mmap)
+    }
+
+    /// Eén-pas parser over een ruwe byte-slice: de `p edge n m`-regel
+    /// reserveert de capaciteit voor de kanten, en elke `e u v`-regel wordt
+    /// rechtstreeks uit de slice gelezen via `parse_uint_at`, zonder per
+    /// regel of per token een `String` te alloceren. Er is geen aparte
+    /// eerste pas over de data.
+    fn parse_dimacs_bytes(data: &[u8]) -> io::Result<Self> {
+        let mut n = 0usize;
+        let mut edges: Vec<(usize, usize)> = Vec::new();
+        let mut header_found = false;
+
+        for raw_line in data.split(|&b| b == b'\n') {
+            let line = trim_ascii(raw_line);
+            if line.is_empty() || line[0] == b'c' {
+                continue;
+            }
+
+            if line[0] == b'p' {
+                let pos = skip_non_digits(line, 0);
+                let (parsed_n, pos) = parse_uint_at(line, pos);
+                let pos = skip_non_digits(line, pos);
+                let (parsed_m, _) = parse_uint_at(line, pos);
+                n = parsed_n;
+                edges.reserve(parsed_m);
+                header_found = true;
+            } else if line[0] == b'e' {
+                if !header_found {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, "Edge line 'e' found before problem line 'p'"));
+                }
+                let pos = skip_non_digits(line, 0);
+                let (u, pos) = parse_uint_at(line, pos);
+                let pos = skip_non_digits(line, pos);
+                let (v, _) = parse_uint_at(line, pos);
+
+                if u > 0 && v > 0 && u <= n && v <= n {
+                    edges.push((u - 1, v - 1));
+                } else {
+                    return Err(io::Error::new(io::ErrorKind::InvalidData, format!("Edge ({}, {}) out of bounds for n={}", u, v, n)));
+                }
+            }
+        }
+        Ok(Self::from_edge_list(n, &edges))
+    }
+    // --- EINDE NIEUW ---
+
     /*────────── Getters ──────────*/
 
     /// Geeft het aantal knopen (vertices) in de graaf terug.
@@ -121,4 +220,69 @@ impl Graph {
         self.adj[u].set(v, true);
         self.adj[v].set(u, true);
     }
+
+    /*────────── Samenhangscomponenten ──────────*/
+
+    // --- NIEUW ---
+    /// Berekent de samenhangscomponenten van de graaf via iteratieve BFS
+    /// over `neigh_row`. Een γ-quasi-clique kan nooit twee componenten
+    /// overspannen, dus dit dient als voorbewerking om de zoekruimte te
+    /// beperken — zie `maxk::solve_maxk_by_components`.
+    pub fn connected_components(&self) -> Vec<BitVec> {
+        let n = self.n();
+        let mut visited = bitvec![0; n];
+        let mut components = Vec::new();
+
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            let mut component = bitvec![0; n];
+            let mut queue = VecDeque::new();
+            queue.push_back(start);
+            visited.set(start, true);
+            component.set(start, true);
+
+            while let Some(u) = queue.pop_front() {
+                for v in self.neigh_row(u).iter_ones() {
+                    if !visited[v] {
+                        visited.set(v, true);
+                        component.set(v, true);
+                        queue.push_back(v);
+                    }
+                }
+            }
+
+            components.push(component);
+        }
+
+        components
+    }
+
+    /// Bouwt de geïnduceerde deelgraaf op de knopen in `vertices` (bv. één
+    /// component uit `connected_components`). Geeft naast de deelgraaf ook
+    /// de afbeelding van nieuwe naar originele knoopindices terug
+    /// (`mapping[nieuwe_index] == originele_index`), zodat een op de
+    /// deelgraaf gevonden oplossing achteraf teruggeschreven kan worden
+    /// naar de knoopindices van de originele graaf.
+    pub fn induced_subgraph(&self, vertices: &bitvec::slice::BitSlice) -> (Graph, Vec<usize>) {
+        let mapping: Vec<usize> = vertices.iter_ones().collect();
+        let mut index_of = vec![usize::MAX; self.n()];
+        for (new_idx, &orig) in mapping.iter().enumerate() {
+            index_of[orig] = new_idx;
+        }
+
+        let mut edges = Vec::new();
+        for (new_u, &orig_u) in mapping.iter().enumerate() {
+            for orig_v in self.neigh_row(orig_u).iter_ones() {
+                let new_v = index_of[orig_v];
+                if new_v != usize::MAX && new_v > new_u {
+                    edges.push((new_u, new_v));
+                }
+            }
+        }
+
+        (Graph::from_edge_list(mapping.len(), &edges), mapping)
+    }
+    // --- EINDE NIEUW ---
 }
\ No newline at end of file
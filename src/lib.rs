@@ -1,12 +1,17 @@
 // src/lib.rs
 
 // Publieke modules voor gebruik binnen de Rust-crate
+pub mod activity;
+pub mod checkpoint;
 pub mod construct;
 pub mod diversify;
+pub mod exact;
 pub mod graph;
 pub mod lns;
+pub mod matching;
 pub mod maxk;
 pub mod mcts;
+pub mod metrics;
 pub mod neighbour;
 pub mod params;
 pub mod restart;
@@ -25,18 +30,108 @@ use rand::SeedableRng;
 use std::fs::File;
 use std::io::BufReader;
 
+// --- NIEUW ---
+/// Laadt een DIMACS-instantie, via de memory-mapped parser wanneer
+/// `use_mmap` is gezet en anders via de gebufferde reader. Gedeeld door
+/// `solve_k_py`, `solve_k_with_metrics_py` en `solve_max_py` zodat de
+/// mmap/fallback-keuze niet drie keer apart hoeft te worden uitgeschreven.
+fn load_graph(instance_path: &str, use_mmap: bool) -> PyResult<Graph> {
+    if use_mmap {
+        Graph::parse_dimacs_mmap(instance_path)
+           .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    } else {
+        let file = File::open(instance_path)
+           .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
+        Graph::parse_dimacs(BufReader::new(file))
+           .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))
+    }
+}
+
+/// Laadt, indien `resume` is gezet en `checkpoint_path` naar een bestaand
+/// checkpoint-bestand wijst, de bewaarde beste oplossing en de run-index
+/// waarbij de sweep moet hervatten. Anders begint de sweep bij run 0 met
+/// een lege oplossing.
+fn resume_from_checkpoint<'g>(
+    graph: &'g Graph,
+    checkpoint_path: &Option<String>,
+    resume: bool,
+) -> PyResult<(Solution<'g>, usize)> {
+    if resume {
+        if let Some(path) = checkpoint_path {
+            if let Some(cp) = checkpoint::Checkpoint::load(path)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?
+            {
+                return Ok((cp.to_solution(graph), cp.next_run_index));
+            }
+        }
+    }
+    Ok((Solution::new(graph), 0))
+}
+
+/// Schrijft, indien `checkpoint_path` is gezet, de huidige beste oplossing
+/// en de volgende run-index atomisch weg naar dat pad.
+fn save_checkpoint(
+    checkpoint_path: &Option<String>,
+    best_sol_overall: &Solution,
+    next_run_index: usize,
+) -> PyResult<()> {
+    if let Some(path) = checkpoint_path {
+        let cp = checkpoint::Checkpoint {
+            members: best_sol_overall.bitset().iter_ones().collect(),
+            next_run_index,
+        };
+        cp.save(path)
+           .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
+    }
+    Ok(())
+}
+// --- EINDE NIEUW ---
+
+// --- NIEUW ---
+/// Eén resultaatrecord van een enkele herstart-run: seed, resulterende
+/// grootte/randen/dichtheid, of `gamma_target` gehaald werd, het aantal
+/// verbruikte iteraties, en of de run een timeout raakte. Teruggegeven door
+/// `solve_k_runs_py`/`solve_max_runs_py` zodat een aanroeper de volledige
+/// verdeling over `p.runs` onafhankelijke restarts kan analyseren
+/// (gemiddelde/mediaan/beste/tijd-tot-doel) in plaats van enkel de beste
+/// oplossing te zien.
+type RunRecord = (u64, usize, usize, f64, bool, usize, bool);
+
+/// Voert één fixed-k run uit met het gegeven `seed` en geeft zowel de
+/// gevonden oplossing als het bijbehorende resultaatrecord terug. Gedeeld
+/// door `solve_k_py` en `solve_k_runs_py` zodat de aggregaatfunctie een
+/// dunne reducer over deze per-run-API blijft.
+fn run_k_once<'g>(graph: &'g Graph, k_val: usize, p: &Params, seed: u64) -> (Solution<'g>, RunRecord) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut metrics = metrics::MetricsRegistry::new();
+    let (sol, timed_out, stats) = restart::solve_fixed_k(graph, k_val, &mut rng, p, &mut metrics);
+    let record = (seed, sol.size(), sol.edges(), sol.density(), stats.hit_target, stats.iterations, timed_out);
+    (sol, record)
+}
+
+/// Voert één max-k run uit met het gegeven `seed`, analoog aan `run_k_once`
+/// maar via `maxk::solve_maxk_by_components`.
+fn run_max_once<'g>(graph: &'g Graph, p: &Params, seed: u64) -> (Solution<'g>, RunRecord) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut metrics = metrics::MetricsRegistry::new();
+    let (sol, timed_out, stats) = maxk::solve_maxk_by_components(graph, &mut rng, p, &mut metrics);
+    let record = (seed, sol.size(), sol.edges(), sol.density(), stats.hit_target, stats.iterations, timed_out);
+    (sol, record)
+}
+// --- EINDE NIEUW ---
+
 /// Python-binding voor de fixed-k oplosser.
 #[pyfunction]
-#[pyo3(signature = (instance_path, py_params))]
+#[pyo3(signature = (instance_path, py_params, use_mmap = false, checkpoint_path = None, resume = false))]
 fn solve_k_py(
     instance_path: String,
     py_params: Py<Params>,
+    use_mmap: bool,
+    checkpoint_path: Option<String>,
+    resume: bool,
 ) -> PyResult<(usize, usize, f64, bool)> {
-    let file = File::open(&instance_path)
-       .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
-    let graph = Graph::parse_dimacs(BufReader::new(file))
-       .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
-    
+    let graph = load_graph(&instance_path, use_mmap)?;
+
     let p = Python::with_gil(|py| {
         let p_ref = py_params.borrow(py);
         Params::new(
@@ -57,23 +152,69 @@ fn solve_k_py(
             p_ref.k,
             p_ref.runs,
             p_ref.seed,
+            // --- NIEUW ---
+            p_ref.restart_fast_window,
+            p_ref.restart_slow_window,
+            p_ref.restart_margin_k,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.rephase_prob,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.activity_decay_start,
+            p_ref.activity_decay_end,
+            p_ref.activity_weight,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.exact_max_n,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.sls_trigger,
+            p_ref.sls_noise,
+            p_ref.sls_walk_steps,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.beam_width,
+            p_ref.beam_patience,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_batch_swap,
+            p_ref.batch_swap_tolerance,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_transposition_cache,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_component_restriction,
+            p_ref.component_min_size,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_beam_search,
+            // --- EINDE NIEUW ---
         )
     });
-    
+
     let k_val = p.k.expect("Fixed-k mode requires a 'k' value in Params.");
 
-    let mut best_sol_overall = Solution::new(&graph);
+    // --- NIEUW ---
+    let (mut best_sol_overall, start_run) = resume_from_checkpoint(&graph, &checkpoint_path, resume)?;
+    // --- EINDE NIEUW ---
     let mut is_timed_out_overall = false;
 
-    for i in 0..p.runs {
-        let mut rng = StdRng::seed_from_u64(p.seed + i as u64);
-        let (sol, timed_out_run) = restart::solve_fixed_k(&graph, k_val, &mut rng, &p);
-        if sol.density() > best_sol_overall.density() {
+    for i in start_run..p.runs {
+        // --- NIEUW ---
+        let (sol, record) = run_k_once(&graph, k_val, &p, p.seed + i as u64);
+        let (_, _, _, density, _, _, timed_out_run) = record;
+        // --- EINDE NIEUW ---
+        if density > best_sol_overall.density() {
             best_sol_overall = sol;
         }
         if timed_out_run {
             is_timed_out_overall = true;
         }
+        // --- NIEUW ---
+        save_checkpoint(&checkpoint_path, &best_sol_overall, i + 1)?;
+        // --- EINDE NIEUW ---
     }
 
     Ok((
@@ -84,17 +225,170 @@ fn solve_k_py(
     ))
 }
 
+// --- NIEUW ---
+/// Python-binding die de volledige verdeling van per-run resultaten
+/// teruggeeft in plaats van enkel de beste oplossing (zie `RunRecord`): elk
+/// element bevat `(seed, size, edges, density, hit_target, iterations,
+/// timed_out)` voor één van de `p.runs` onafhankelijke restarts, zodat een
+/// aanroeper zelf gemiddelde/mediaan/beste/tijd-tot-doel kan berekenen.
+#[pyfunction]
+#[pyo3(signature = (instance_path, py_params, use_mmap = false))]
+fn solve_k_runs_py(
+    instance_path: String,
+    py_params: Py<Params>,
+    use_mmap: bool,
+) -> PyResult<Vec<RunRecord>> {
+    let graph = load_graph(&instance_path, use_mmap)?;
+
+    let p = Python::with_gil(|py| {
+        let p_ref = py_params.borrow(py);
+        Params::new(
+            p_ref.gamma_target,
+            p_ref.stagnation_iter,
+            p_ref.max_iter,
+            p_ref.tenure_u,
+            p_ref.tenure_v,
+            p_ref.use_mcts,
+            p_ref.mcts_budget,
+            p_ref.mcts_exploration_const,
+            p_ref.mcts_max_depth,
+            p_ref.lns_repair_depth,
+            p_ref.lns_rcl_alpha,
+            p_ref.max_time_seconds,
+            p_ref.k,
+            p_ref.runs,
+            p_ref.seed,
+            p_ref.restart_fast_window,
+            p_ref.restart_slow_window,
+            p_ref.restart_margin_k,
+            p_ref.rephase_prob,
+            p_ref.activity_decay_start,
+            p_ref.activity_decay_end,
+            p_ref.activity_weight,
+            p_ref.exact_max_n,
+            p_ref.sls_trigger,
+            p_ref.sls_noise,
+            p_ref.sls_walk_steps,
+            p_ref.beam_width,
+            p_ref.beam_patience,
+            p_ref.use_batch_swap,
+            p_ref.batch_swap_tolerance,
+            p_ref.use_transposition_cache,
+            p_ref.use_component_restriction,
+            p_ref.component_min_size,
+            p_ref.use_beam_search,
+        )
+    });
+
+    let k_val = p.k.expect("Fixed-k mode requires a 'k' value in Params.");
+
+    let mut records = Vec::with_capacity(p.runs);
+    for i in 0..p.runs {
+        let (_, record) = run_k_once(&graph, k_val, &p, p.seed + i as u64);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+// --- EINDE NIEUW ---
+
+// --- NIEUW ---
+/// Python-binding voor de fixed-k oplosser die ook een Prometheus text
+/// exposition-string teruggeeft, opgebouwd uit de per-run `MetricsRegistry`
+/// (iteraties, restarts, geaccepteerde/verworpen tabu-zetten, LNS-herstellen,
+/// MCTS-rollouts en dichtheidsgauges). Laat de bestaande `solve_k_py` verder
+/// ongemoeid zodat de huidige API niet verandert.
+#[pyfunction]
+#[pyo3(signature = (instance_path, py_params, use_mmap = false))]
+fn solve_k_with_metrics_py(
+    instance_path: String,
+    py_params: Py<Params>,
+    use_mmap: bool,
+) -> PyResult<((usize, usize, f64, bool), String)> {
+    let graph = load_graph(&instance_path, use_mmap)?;
+
+    let p = Python::with_gil(|py| {
+        let p_ref = py_params.borrow(py);
+        Params::new(
+            p_ref.gamma_target,
+            p_ref.stagnation_iter,
+            p_ref.max_iter,
+            p_ref.tenure_u,
+            p_ref.tenure_v,
+            p_ref.use_mcts,
+            p_ref.mcts_budget,
+            p_ref.mcts_exploration_const,
+            p_ref.mcts_max_depth,
+            p_ref.lns_repair_depth,
+            p_ref.lns_rcl_alpha,
+            p_ref.max_time_seconds,
+            p_ref.k,
+            p_ref.runs,
+            p_ref.seed,
+            p_ref.restart_fast_window,
+            p_ref.restart_slow_window,
+            p_ref.restart_margin_k,
+            p_ref.rephase_prob,
+            p_ref.activity_decay_start,
+            p_ref.activity_decay_end,
+            p_ref.activity_weight,
+            p_ref.exact_max_n,
+            p_ref.sls_trigger,
+            p_ref.sls_noise,
+            p_ref.sls_walk_steps,
+            p_ref.beam_width,
+            p_ref.beam_patience,
+            p_ref.use_batch_swap,
+            p_ref.batch_swap_tolerance,
+            p_ref.use_transposition_cache,
+            p_ref.use_component_restriction,
+            p_ref.component_min_size,
+            p_ref.use_beam_search,
+        )
+    });
+
+    let k_val = p.k.expect("Fixed-k mode requires a 'k' value in Params.");
+
+    let mut best_sol_overall = Solution::new(&graph);
+    let mut is_timed_out_overall = false;
+    let mut metrics_text = String::new();
+
+    for i in 0..p.runs {
+        let mut rng = StdRng::seed_from_u64(p.seed + i as u64);
+        let mut metrics = metrics::MetricsRegistry::new();
+        let (sol, timed_out_run, _stats) = restart::solve_fixed_k(&graph, k_val, &mut rng, &p, &mut metrics);
+        metrics_text.push_str(&metrics.render_prometheus(i));
+        if sol.density() > best_sol_overall.density() {
+            best_sol_overall = sol;
+        }
+        if timed_out_run {
+            is_timed_out_overall = true;
+        }
+    }
+
+    Ok((
+        (
+            best_sol_overall.size(),
+            best_sol_overall.edges(),
+            best_sol_overall.density(),
+            is_timed_out_overall,
+        ),
+        metrics_text,
+    ))
+}
+// --- EINDE NIEUW ---
+
 /// Python-binding voor de max-k oplosser.
 #[pyfunction]
-#[pyo3(signature = (instance_path, py_params))]
+#[pyo3(signature = (instance_path, py_params, use_mmap = false, checkpoint_path = None, resume = false))]
 fn solve_max_py(
     instance_path: String,
     py_params: Py<Params>,
+    use_mmap: bool,
+    checkpoint_path: Option<String>,
+    resume: bool,
 ) -> PyResult<(usize, usize, f64, bool)> {
-    let file = File::open(&instance_path)
-       .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(e.to_string()))?;
-    let graph = Graph::parse_dimacs(BufReader::new(file))
-       .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    let graph = load_graph(&instance_path, use_mmap)?;
 
     let p = Python::with_gil(|py| {
         let p_ref = py_params.borrow(py);
@@ -116,23 +410,69 @@ fn solve_max_py(
             p_ref.k,
             p_ref.runs,
             p_ref.seed,
+            // --- NIEUW ---
+            p_ref.restart_fast_window,
+            p_ref.restart_slow_window,
+            p_ref.restart_margin_k,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.rephase_prob,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.activity_decay_start,
+            p_ref.activity_decay_end,
+            p_ref.activity_weight,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.exact_max_n,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.sls_trigger,
+            p_ref.sls_noise,
+            p_ref.sls_walk_steps,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.beam_width,
+            p_ref.beam_patience,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_batch_swap,
+            p_ref.batch_swap_tolerance,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_transposition_cache,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_component_restriction,
+            p_ref.component_min_size,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            p_ref.use_beam_search,
+            // --- EINDE NIEUW ---
         )
     });
 
-    let mut best_sol_overall = Solution::new(&graph);
+    // --- NIEUW ---
+    let (mut best_sol_overall, start_run) = resume_from_checkpoint(&graph, &checkpoint_path, resume)?;
+    // --- EINDE NIEUW ---
     let mut is_timed_out_overall = false;
 
-    for i in 0..p.runs {
-        let mut rng = StdRng::seed_from_u64(p.seed + i as u64);
-        let (sol, timed_out_run) = maxk::solve_maxk(&graph, &mut rng, &p);
-        if sol.size() > best_sol_overall.size()
-            || (sol.size() == best_sol_overall.size() && sol.density() > best_sol_overall.density())
+    for i in start_run..p.runs {
+        // --- NIEUW ---
+        let (sol, record) = run_max_once(&graph, &p, p.seed + i as u64);
+        let (_, size, _, density, _, _, timed_out_run) = record;
+        // --- EINDE NIEUW ---
+        if size > best_sol_overall.size()
+            || (size == best_sol_overall.size() && density > best_sol_overall.density())
         {
             best_sol_overall = sol;
         }
         if timed_out_run {
             is_timed_out_overall = true;
         }
+        // --- NIEUW ---
+        save_checkpoint(&checkpoint_path, &best_sol_overall, i + 1)?;
+        // --- EINDE NIEUW ---
     }
 
     Ok((
@@ -143,6 +483,68 @@ fn solve_max_py(
     ))
 }
 
+// --- NIEUW ---
+/// Python-binding die de volledige verdeling van per-run resultaten voor de
+/// max-k oplosser teruggeeft, analoog aan `solve_k_runs_py`.
+#[pyfunction]
+#[pyo3(signature = (instance_path, py_params, use_mmap = false))]
+fn solve_max_runs_py(
+    instance_path: String,
+    py_params: Py<Params>,
+    use_mmap: bool,
+) -> PyResult<Vec<RunRecord>> {
+    let graph = load_graph(&instance_path, use_mmap)?;
+
+    let p = Python::with_gil(|py| {
+        let p_ref = py_params.borrow(py);
+        Params::new(
+            p_ref.gamma_target,
+            p_ref.stagnation_iter,
+            p_ref.max_iter,
+            p_ref.tenure_u,
+            p_ref.tenure_v,
+            p_ref.use_mcts,
+            p_ref.mcts_budget,
+            p_ref.mcts_exploration_const,
+            p_ref.mcts_max_depth,
+            p_ref.lns_repair_depth,
+            p_ref.lns_rcl_alpha,
+            p_ref.max_time_seconds,
+            p_ref.k,
+            p_ref.runs,
+            p_ref.seed,
+            p_ref.restart_fast_window,
+            p_ref.restart_slow_window,
+            p_ref.restart_margin_k,
+            p_ref.rephase_prob,
+            p_ref.activity_decay_start,
+            p_ref.activity_decay_end,
+            p_ref.activity_weight,
+            p_ref.exact_max_n,
+            p_ref.sls_trigger,
+            p_ref.sls_noise,
+            p_ref.sls_walk_steps,
+            p_ref.beam_width,
+            p_ref.beam_patience,
+            p_ref.use_batch_swap,
+            p_ref.batch_swap_tolerance,
+            p_ref.use_transposition_cache,
+            p_ref.use_component_restriction,
+            p_ref.component_min_size,
+            p_ref.use_beam_search,
+        )
+    });
+
+    let mut records = Vec::with_capacity(p.runs);
+    for i in 0..p.runs {
+        let (_, record) = run_max_once(&graph, &p, p.seed + i as u64);
+        records.push(record);
+    }
+
+    Ok(records)
+}
+// --- EINDE NIEUW ---
+
 /// Helperfunctie om een DIMACS-bestand te parsen en (n, m) terug te geven.
 #[pyfunction]
 fn parse_dimacs_py(instance_path: String) -> PyResult<(usize, usize)> {
@@ -153,13 +555,34 @@ fn parse_dimacs_py(instance_path: String) -> PyResult<(usize, usize)> {
     Ok((graph.n(), graph.m()))
 }
 
+// --- NIEUW ---
+/// Helperfunctie om een DIMACS-bestand via de memory-mapped parser
+/// (`Graph::parse_dimacs_mmap`) te parsen en (n, m) terug te geven.
+#[pyfunction]
+fn parse_dimacs_mmap_py(instance_path: String) -> PyResult<(usize, usize)> {
+    let graph = Graph::parse_dimacs_mmap(&instance_path)
+       .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+    Ok((graph.n(), graph.m()))
+}
+// --- EINDE NIEUW ---
+
 
 /// Definieert de Python-module `_native`.
 #[pymodule]
 fn _native(_py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Params>()?;
     m.add_function(wrap_pyfunction!(solve_k_py, m)?)?;
+    // --- NIEUW ---
+    m.add_function(wrap_pyfunction!(solve_k_with_metrics_py, m)?)?;
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    m.add_function(wrap_pyfunction!(solve_k_runs_py, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_max_runs_py, m)?)?;
+    // --- EINDE NIEUW ---
     m.add_function(wrap_pyfunction!(solve_max_py, m)?)?;
     m.add_function(wrap_pyfunction!(parse_dimacs_py, m)?)?;
+    // --- NIEUW ---
+    m.add_function(wrap_pyfunction!(parse_dimacs_mmap_py, m)?)?;
+    // --- EINDE NIEUW ---
     Ok(())
 }
\ No newline at end of file
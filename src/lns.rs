@@ -1,24 +1,61 @@
 // src/lns.rs
 
 use crate::{
-    graph::Graph, // --- TOEGEVOEGD: expliciete import voor count_connections ---
-    neighbour::improve_once, 
-    params::Params, 
-    solution::Solution, 
+    activity::Activity, // --- NIEUW: LRB/EVSIDS-achtige bias op de RCL-selectie ---
+    metrics::MetricsRegistry, // --- NIEUW: registreert lns_repairs rechtstreeks aan de bron ---
+    neighbour::improve_once,
+    params::Params,
+    solution::Solution,
     tabu::DualTabu,
 };
 use rand::seq::SliceRandom; // --- TOEGEVOEGD: voor .choose() op de RCL ---
 use rand::Rng;
+use std::collections::BinaryHeap; // --- NIEUW: incrementele gain-heap voor de greedy completion ---
+
+// --- NIEUW ---
+/// Vergelijkbare sleutel voor de gain-heap hieronder. `BinaryHeap` vereist
+/// `Ord`, dat `f64` niet heeft omdat NaN geen totale ordening toelaat; deze
+/// wrapper delegeert naar `partial_cmp` (hetzelfde patroon als elders in
+/// deze crate, bv. `mcts.rs`'s `uct_score`-vergelijkingen), waarbij NaN als
+/// gelijk aan elke andere waarde geldt.
+#[derive(Clone, Copy, PartialEq)]
+struct GainKey(f64);
+
+impl Eq for GainKey {}
+
+impl PartialOrd for GainKey {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for GainKey {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(std::cmp::Ordering::Equal)
+    }
+}
+// --- EINDE NIEUW ---
 
 pub fn apply_lns<'g, R>(
     initial_sol: &Solution<'g>,
     removals: &[usize],
     p: &Params,
     rng: &mut R,
+    // --- NIEUW: de persistente, over de hele run geannealde activiteitstracker
+    // van de caller (zie `restart::solve_fixed_k`), in plaats van een lokaal,
+    // nooit-geannealde exemplaar. Zo weegt de RCL-selectie hier ook mee op
+    // knopen met aanhoudende historische payoff, net als in `improve_once`.
+    act: &mut Activity,
+    // --- EINDE NIEUW ---
+    // --- NIEUW: registreert deze herstelpoging rechtstreeks, in plaats van
+    // dat de caller het via een parallelle `restart_count % 3`-controle raadt.
+    metrics: &mut MetricsRegistry,
+    // --- EINDE NIEUW ---
 ) -> Solution<'g>
 where
     R: Rng + ?Sized,
 {
+    metrics.inc("lns_repairs");
     let mut sol = initial_sol.clone();
     for &v in removals {
         sol.remove(v);
@@ -28,46 +65,88 @@ where
     let graph = initial_sol.graph();
 
     // --- FASE 1: Gerandomiseerde Greedy Completion (GRASP) ---
-    // Deze hele `while`-lus is de nieuwe, slimmere logica.
+    // GEOPTIMALISEERD: in plaats van bij elke toevoeging alle resterende
+    // kandidaten te herscannen (O(target_k * n) in totaal), onderhouden we
+    // één max-heap van (gain, vertex) en werken we na elke toevoeging
+    // alleen de gains van de buren van de zojuist toegevoegde knoop bij
+    // (elk wint precies +1 connectie). Stale entries — een knoop die
+    // inmiddels in S zit, of wiens gain is veranderd sinds hij werd
+    // gepusht — worden lazy herkend door het opgeslagen gain-cijfer te
+    // vergelijken met de actuele waarde in `current_gain`, en gewoon
+    // overgeslagen in plaats van fysiek uit de heap verwijderd.
+    // --- NIEUW: de gain bevat, naast de pure connectie-telling, ook
+    // `p.activity_weight * act.get(v)` als additieve bias, onderhouden in de
+    // heap i.p.v. elke iteratie volledig herberekend.
+    let mut current_gain: Vec<f64> = (0..graph.n())
+        .map(|v| {
+            if sol.bitset()[v] {
+                0.0
+            } else {
+                sol.deg_in_s(v) as f64 + p.activity_weight * act.get(v)
+            }
+        })
+        .collect();
+    let mut heap: BinaryHeap<(GainKey, usize)> = (0..graph.n())
+        .filter(|&v| !sol.bitset()[v])
+        .map(|v| (GainKey(current_gain[v]), v))
+        .collect();
+    // --- EINDE NIEUW ---
+
     while sol.size() < target_k {
-        let sol_bitset = sol.bitset();
-        
-        // Stap 1: Verzamel alle mogelijke kandidaten buiten de oplossing en hun 'gain'.
-        let candidates: Vec<(usize, isize)> = (0..graph.n())
-            .filter(|&v| !sol_bitset[v])
-            .map(|v| {
-                // Gebruik de `count_connections` methode van Solution, die al geoptimaliseerd is.
-                let gain = sol.count_connections(v) as isize;
-                (v, gain)
-            })
-            .collect();
+        // Stap 1+2: pop knopen van de heap tot aan de alpha-drempel onder de
+        // beste nog-geldige gain, en verzamel ze als Restricted Candidate List.
+        let mut rcl: Vec<usize> = Vec::new();
+        let mut best_gain: Option<f64> = None;
+
+        loop {
+            let top = match heap.peek() {
+                Some(&(g, v)) => (g.0, v),
+                None => break,
+            };
+            let (g, v) = top;
+            if sol.bitset()[v] || current_gain[v] != g {
+                // Stale: v zit al in S, of de gain is intussen veranderd.
+                heap.pop();
+                continue;
+            }
+            let threshold = match best_gain {
+                None => {
+                    best_gain = Some(g);
+                    g
+                }
+                Some(bg) => bg * p.lns_rcl_alpha,
+            };
+            if g < threshold {
+                // De heap is dalend gesorteerd: alles hierna valt ook buiten de band.
+                break;
+            }
+            rcl.push(v);
+            heap.pop();
+        }
 
         // Als er geen kandidaten meer zijn, kunnen we niet verder.
-        if candidates.is_empty() {
+        if rcl.is_empty() {
             break;
         }
 
-        // Stap 2: Bepaal de hoogst mogelijke gain van alle kandidaten.
-        let best_gain = match candidates.iter().map(|&(_, g)| g).max() {
-            Some(g) => g,
-            None => break, // Veiligheid: stop als de lijst leeg zou zijn.
-        };
-        
-        // Stap 3: Bouw de Restricted Candidate List (RCL).
-        // Bepaal de drempel op basis van de beste gain en de nieuwe alpha-parameter.
-        let rcl_threshold = (best_gain as f64 * p.lns_rcl_alpha).floor() as isize;
-        let rcl: Vec<usize> = candidates
-            .into_iter()
-            .filter(|&(_, g)| g >= rcl_threshold) // Alle kandidaten die 'goed genoeg' zijn.
-            .map(|(v, _)| v)
-            .collect();
-
-        // Stap 4: Kies een WILLEKEURIGE knoop uit de lijst van goede kandidaten en voeg toe.
-        if let Some(&chosen) = rcl.choose(rng) {
-            sol.add(chosen);
-        } else {
-            // Als de RCL om een of andere reden leeg is, kunnen we niet verder.
-            break;
+        // Stap 3: Kies een WILLEKEURIGE knoop uit de RCL en voeg toe.
+        let chosen = *rcl.choose(rng).unwrap();
+
+        // De overige RCL-kandidaten horen nog steeds bij de heap: terugzetten
+        // met hun (nog) ongewijzigde gain.
+        for &v in rcl.iter().filter(|&&v| v != chosen) {
+            heap.push((GainKey(current_gain[v]), v));
+        }
+
+        sol.add(chosen);
+        act.bump(chosen);
+
+        // Stap 4: werk alleen de buren van `chosen` incrementeel bij.
+        for u in graph.neigh_row(chosen).iter_ones() {
+            if !sol.bitset()[u] {
+                current_gain[u] += 1.0;
+                heap.push((GainKey(current_gain[u]), u));
+            }
         }
     }
     // --- EINDE NIEUWE LOGICA ---
@@ -78,11 +157,11 @@ where
     // proberen we de oplossing lokaal nog wat te verbeteren.
     if p.lns_repair_depth > 0 {
         let mut tabu = DualTabu::new(graph.n(), p.tenure_u, p.tenure_v);
-        let mut freq = vec![0; graph.n()]; 
+        let mut freq = vec![0; graph.n()];
         let best_rho = 0.0;
 
         for _ in 0..p.lns_repair_depth {
-            if !improve_once(&mut sol, &mut tabu, best_rho, &mut freq, p, rng) {
+            if !improve_once(&mut sol, &mut tabu, best_rho, &mut freq, act, metrics, p, rng) {
                 break;
             }
         }
@@ -0,0 +1,181 @@
+// src/matching.rs
+//! Maximum-gewicht bipartiete matching via het Hongaarse algoritme
+//! (Kuhn–Munkres), gebruikt door de "batch swap"-modus in `neighbour.rs`
+//! om meerdere simultane swaps tussen de kritieke sets `set_a` en
+//! `set_b` in één keer te selecteren, in plaats van telkens één beste
+//! swap te kiezen.
+//!
+//! `w[i][j]` is `Some(gewicht)` voor een toegestane swap tussen de i-de
+//! knoop van de linkerkant en de j-de knoop van de rechterkant, en
+//! `None` voor een niet-toegestane combinatie (bv. een niet-taboe swap
+//! met een niet-positieve delta). Knopen mogen onbezet blijven: intern
+//! wordt de matrix opgevuld met "niet toewijzen"-dummy's, zodat een
+//! perfecte toewijzing op de opgevulde matrix overeenkomt met een
+//! optimale *partiële* matching op de oorspronkelijke matrix.
+
+const FORBIDDEN: f64 = -1e15;
+
+/// Lost het maximum-gewicht bipartiete matchingprobleem op. Geeft voor
+/// elke rij `i` de gekozen kolom terug, of `None` als rij `i` onbezet
+/// blijft (hetzij omdat dat optimaal is, hetzij omdat er geen
+/// toegestane swap voor overblijft).
+pub fn max_weight_matching(w: &[Vec<Option<f64>>]) -> Vec<Option<usize>> {
+    let rows = w.len();
+    if rows == 0 {
+        return Vec::new();
+    }
+    let cols = w[0].len();
+    if cols == 0 {
+        return vec![None; rows];
+    }
+
+    // Uitgebreide matrix: linkerknopen = echte rijen + "onbezet"-dummy's
+    // voor elke kolom; rechterknopen = echte kolommen + "onbezet"-dummy's
+    // voor elke rij. Zo kan elke echte knoop kosteloos onbezet blijven,
+    // zonder de optimale partiële matching te verstoren.
+    let n = rows + cols;
+    let mut cost = vec![vec![0.0_f64; n]; n];
+
+    for (i, row) in cost.iter_mut().enumerate().take(rows) {
+        for (j, cell) in row.iter_mut().enumerate().take(cols) {
+            let real_weight = w[i][j].unwrap_or(FORBIDDEN);
+            *cell = -real_weight;
+        }
+        for k in 0..rows {
+            row[cols + k] = if k == i { 0.0 } else { -FORBIDDEN };
+        }
+    }
+    for k in 0..cols {
+        let r = rows + k;
+        for j in 0..cols {
+            cost[r][j] = if k == j { 0.0 } else { -FORBIDDEN };
+        }
+        for k2 in 0..rows {
+            cost[r][cols + k2] = 0.0;
+        }
+    }
+
+    let assignment = hungarian_min(&cost);
+
+    let mut result = vec![None; rows];
+    for (i, slot) in result.iter_mut().enumerate() {
+        let j = assignment[i];
+        if j < cols && w[i][j].is_some() {
+            *slot = Some(j);
+        }
+    }
+    result
+}
+
+/// Klassieke O(n^3) Hongaarse algoritme (primal-dual) voor een vierkante
+/// kostenmatrix; minimaliseert de totale kost van een perfecte
+/// toewijzing. Gebaseerd op de standaardformulering met potentialen
+/// (zie cp-algorithms.com/graph/hungarian-algorithm).
+fn hungarian_min(cost: &[Vec<f64>]) -> Vec<usize> {
+    let n = cost.len();
+    let inf = f64::INFINITY;
+    let mut u = vec![0.0_f64; n + 1];
+    let mut v = vec![0.0_f64; n + 1];
+    let mut p = vec![0usize; n + 1]; // p[j] = rij (1-based) toegewezen aan kolom j
+    let mut way = vec![0usize; n + 1];
+
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0usize;
+        let mut minv = vec![inf; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = inf;
+            let mut j1 = 0usize;
+            for j in 1..=n {
+                if !used[j] {
+                    let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                    if cur < minv[j] {
+                        minv[j] = cur;
+                        way[j] = j0;
+                    }
+                    if minv[j] < delta {
+                        delta = minv[j];
+                        j1 = j;
+                    }
+                }
+            }
+            for j in 0..=n {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut row_to_col = vec![0usize; n];
+    for j in 1..=n {
+        if p[j] != 0 {
+            row_to_col[p[j] - 1] = j - 1;
+        }
+    }
+    row_to_col
+}
+
+// --- NIEUW ---
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // hungarian_min is private, dus deze test leeft in dezelfde module in
+    // plaats van in tests/ zoals de rest van de suite.
+    #[test]
+    fn hungarian_min_finds_unique_zero_cost_diagonal() {
+        let cost = vec![
+            vec![0.0, 1.0, 1.0],
+            vec![1.0, 0.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+        ];
+        let assignment = hungarian_min(&cost);
+        assert_eq!(assignment, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn max_weight_matching_picks_highest_total_weight() {
+        let w = vec![
+            vec![Some(5.0), Some(1.0)],
+            vec![Some(2.0), Some(3.0)],
+        ];
+        let assignment = max_weight_matching(&w);
+        assert_eq!(assignment, vec![Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn max_weight_matching_respects_forbidden_entries() {
+        let w = vec![
+            vec![Some(5.0), None],
+            vec![Some(2.0), Some(3.0)],
+        ];
+        let assignment = max_weight_matching(&w);
+        assert_eq!(assignment, vec![Some(0), Some(1)]);
+    }
+
+    #[test]
+    fn max_weight_matching_handles_empty_input() {
+        assert_eq!(max_weight_matching(&[]), Vec::<Option<usize>>::new());
+        assert_eq!(max_weight_matching(&[Vec::new()]), vec![None]);
+    }
+}
+// --- EINDE NIEUW ---
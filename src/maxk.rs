@@ -3,7 +3,8 @@
 //! Uitgevoerd door solve_fixed_k herhaald voor oplopende k, stopt bij
 //! ofwel geen γ-feasible oplossing meer, ofwel bij de bovengrens.
 
-use crate::{graph::Graph, restart::solve_fixed_k, solution::Solution, params::Params};
+use crate::{graph::Graph, metrics::MetricsRegistry, restart::{solve_fixed_k, RunStats}, solution::Solution, params::Params};
+use bitvec::prelude::*;
 use rand::Rng;
 use std::time::Instant; // NIEUWE IMPORT: Voor het bijhouden van de tijd
 
@@ -28,7 +29,14 @@ fn ub_edges(prefix: &[usize], k: usize) -> usize {
 
 /// Zoekt naar de maximale γ-quasi-clique door solve_fixed_k
 /// herhaald voor k = 2..n en stopt bij eerste mislukking of bij pruning.
-pub fn solve_maxk<'g, R>(graph: &'g Graph, rng: &mut R, p: &Params) -> (Solution<'g>, bool) // AANGEPAST retourtype
+pub fn solve_maxk<'g, R>(
+    graph: &'g Graph,
+    rng: &mut R,
+    p: &Params,
+    // --- NIEUW ---
+    metrics: &mut MetricsRegistry,
+    // --- EINDE NIEUW ---
+) -> (Solution<'g>, bool, RunStats) // AANGEPAST retourtype
 where
     R: Rng + ?Sized + Send + Sync,
 {
@@ -38,21 +46,24 @@ where
     // Timer initialisatie
     let start_time = Instant::now();
     let mut is_timed_out_maxk = false;
+    // --- NIEUW: telt de totale lokale-zoektocht-iteraties over alle k's heen ---
+    let mut total_iterations = 0usize;
 
     // Geen niet-triviale cliques mogelijk
     if n < 2 {
-        return (Solution::new(graph), false); // Niet timed out
+        return (Solution::new(graph), false, RunStats { iterations: 0, hit_target: false }); // Niet timed out
     }
 
     // Start met k = 2 voor een minimale γ-feasible basis
     let mut best_sol = Solution::new(graph);
-    
+
     // Timeout check voordat de eerste solve_fixed_k wordt aangeroepen
     if p.max_time_seconds > 0.0 && start_time.elapsed().as_secs_f64() >= p.max_time_seconds {
-        return (best_sol, true); // Timed out
+        return (best_sol, true, RunStats { iterations: 0, hit_target: false }); // Timed out
     }
 
-    let (mut sol, timed_out_fixed_k) = solve_fixed_k(graph, 2, rng, p);
+    let (mut sol, timed_out_fixed_k, stats_k2) = solve_fixed_k(graph, 2, rng, p, metrics);
+    total_iterations += stats_k2.iterations;
     if timed_out_fixed_k {
         is_timed_out_maxk = true;
         // Als zelfs de k=2 run timed out, kunnen we hier stoppen of doorgaan.
@@ -61,7 +72,7 @@ where
 
     if !sol.is_gamma_feasible(p.gamma_target) {
         // Geen enkele 2-clique voldoet, dus geen oplossing
-        return (best_sol, is_timed_out_maxk); // Retourneer de best_sol die nog leeg is, en de timeout status
+        return (best_sol, is_timed_out_maxk, RunStats { iterations: total_iterations, hit_target: false }); // Retourneer de best_sol die nog leeg is, en de timeout status
     }
     best_sol = sol;
 
@@ -79,7 +90,33 @@ where
             // Pruning: zelfs in het beste geval te weinig randen
             break;
         }
-        let (sol_k, timed_out_current_k) = solve_fixed_k(graph, k, rng, p);
+
+        // --- NIEUW: voor kleine instanties proberen we eerst de exacte
+        // branch-and-bound solver (gegateerd via `exact_max_n`). Een bewezen
+        // "geen oplossing" laat ons direct stoppen met grotere k (zoals de
+        // heuristische tak hieronder al deed); bij een timeout vallen we
+        // terug op de heuristiek voor deze k.
+        if n <= p.exact_max_n {
+            let (exact_sol, timed_out_exact) = crate::exact::solve_exact_k(graph, k, p, &start_time);
+            if timed_out_exact {
+                is_timed_out_maxk = true;
+            } else {
+                match exact_sol {
+                    Some(sol_k) => {
+                        if sol_k.size() > best_sol.size()
+                            || (sol_k.size() == best_sol.size() && sol_k.density() > best_sol.density())
+                        {
+                            best_sol = sol_k;
+                        }
+                        continue;
+                    }
+                    None => break, // Bewezen: geen γ-feasible subset van grootte k bestaat.
+                }
+            }
+        }
+
+        let (sol_k, timed_out_current_k, stats_k) = solve_fixed_k(graph, k, rng, p, metrics);
+        total_iterations += stats_k.iterations;
         if timed_out_current_k {
             is_timed_out_maxk = true;
         }
@@ -98,5 +135,71 @@ where
         }
     }
 
-    (best_sol, is_timed_out_maxk) // Retourneer de beste oplossing en de timeout status
-}
\ No newline at end of file
+    let hit_target = best_sol.is_gamma_feasible(p.gamma_target);
+    (best_sol, is_timed_out_maxk, RunStats { iterations: total_iterations, hit_target }) // Retourneer de beste oplossing en de timeout status
+}
+
+// --- NIEUW ---
+/// Voert `solve_maxk` uit per samenhangscomponent in plaats van over de
+/// volledige graaf, gegateerd via `p.use_component_restriction`. Een
+/// γ-quasi-clique kan nooit twee componenten overspannen, dus elke
+/// component met minstens `p.component_min_size` knopen wordt apart
+/// doorzocht op zijn eigen, kleinere geïnduceerde deelgraaf
+/// (`graph::induced_subgraph`) — dat krimpt de kandidatenset in
+/// `calculate_critical_degrees`/`build_critical_sets` en `MctsTree`
+/// vanzelf mee (hun `(0..graph.n())`-scans lopen al over de hele graaf
+/// die ze krijgen), zonder dat die functies zelf een aparte
+/// component-parameter hoeven door te geven. De beste oplossing over alle
+/// componenten wordt teruggegeven, teruggeschreven naar de knoopindices
+/// van de originele graaf.
+pub fn solve_maxk_by_components<'g, R>(
+    graph: &'g Graph,
+    rng: &mut R,
+    p: &Params,
+    metrics: &mut MetricsRegistry,
+) -> (Solution<'g>, bool, RunStats)
+where
+    R: Rng + ?Sized + Send + Sync,
+{
+    if !p.use_component_restriction {
+        return solve_maxk(graph, rng, p, metrics);
+    }
+
+    let components = graph.connected_components();
+    let mut best_sol = Solution::new(graph);
+    let mut is_timed_out = false;
+    let mut searched_any = false;
+    let mut total_iterations = 0usize;
+
+    for component in &components {
+        if component.count_ones() < p.component_min_size {
+            continue;
+        }
+        searched_any = true;
+
+        let (sub_graph, mapping) = graph.induced_subgraph(component);
+        let (sub_sol, timed_out, sub_stats) = solve_maxk(&sub_graph, rng, p, metrics);
+        total_iterations += sub_stats.iterations;
+        if timed_out {
+            is_timed_out = true;
+        }
+
+        if sub_sol.size() > best_sol.size()
+            || (sub_sol.size() == best_sol.size() && sub_sol.density() > best_sol.density())
+        {
+            let mut bitset = bitvec![0; graph.n()];
+            for sub_v in sub_sol.bitset().iter_ones() {
+                bitset.set(mapping[sub_v], true);
+            }
+            best_sol = Solution::rebuild_from_bitset(graph, &bitset);
+        }
+    }
+
+    if !searched_any {
+        return solve_maxk(graph, rng, p, metrics);
+    }
+
+    let hit_target = best_sol.is_gamma_feasible(p.gamma_target);
+    (best_sol, is_timed_out, RunStats { iterations: total_iterations, hit_target })
+}
+// --- EINDE NIEUW ---
\ No newline at end of file
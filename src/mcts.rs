@@ -2,8 +2,14 @@
 //!
 //! Implementeert de Monte Carlo Tree Search.
 
-use crate::{graph::Graph, lns::apply_lns, params::Params, solution::Solution};
-use bitvec::prelude::*;
+use crate::{
+    activity::Activity,
+    graph::Graph,
+    lns::apply_lns,
+    metrics::MetricsRegistry, // --- NIEUW: doorgegeven tot in apply_lns, zie rollout/run hieronder ---
+    params::Params,
+    solution::Solution,
+};
 use rand::seq::SliceRandom;
 use rand::Rng;
 use std::collections::{HashMap, HashSet};
@@ -11,10 +17,24 @@ use std::collections::{HashMap, HashSet};
 #[cfg(feature = "parallel_mcts")]
 use rayon::prelude::*;
 
-/// Private helper-functie om de handmatige intersectie-telling uit te voeren.
-fn count_intersecting_ones(a: &BitSlice, b: &BitSlice) -> usize {
-    a.iter().by_vals().zip(b.iter().by_vals()).filter(|&(x, y)| x && y).count()
+// --- NIEUW ---
+/// Order-onafhankelijke FNV-1a fingerprint van een verwijderde-knopenset,
+/// gebruikt als sleutel voor de transpositietabel in `MctsTree`: twee
+/// paden die dezelfde knopen in een andere volgorde verwijderen, moeten
+/// dezelfde cache-entry treffen.
+fn removal_set_fingerprint(path: &[usize]) -> u64 {
+    let mut sorted = path.to_vec();
+    sorted.sort_unstable();
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &v in &sorted {
+        for b in (v as u64).to_le_bytes() {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+    hash
 }
+// --- EINDE NIEUW ---
 
 // VERBETERD: MctsNode is nu publiek voor gebruik in unit tests en de merge-logica.
 #[derive(Clone)]
@@ -33,6 +53,20 @@ pub struct MctsTree<'g> {
     initial_solution: Solution<'g>,
     graph: &'g Graph,
     params: &'g Params,
+    // --- NIEUW ---
+    // Transpositietabel: map van een order-onafhankelijke fingerprint
+    // van de verwijderde-knopenset naar `(som van beloningen, aantal
+    // samples)`, zodat identieke verwijderingssets de gemiddelde
+    // beloning kunnen hergebruiken i.p.v. `apply_lns` opnieuw te draaien.
+    // Enkel actief wanneer `params.use_transposition_cache` aanstaat.
+    transposition: HashMap<u64, (f64, u32)>,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // Telt het aantal uitgevoerde rollouts, voor de Prometheus-metrics
+    // subsystem (zie `metrics.rs`): elke `rollout`-aanroep telt mee, ook
+    // bij een transpositietabel-cache-hit.
+    rollouts: usize,
+    // --- EINDE NIEUW ---
 }
 
 impl<'g> MctsTree<'g> {
@@ -49,10 +83,37 @@ impl<'g> MctsTree<'g> {
             initial_solution: initial_solution.clone(),
             graph,
             params,
+            // --- NIEUW ---
+            transposition: HashMap::new(),
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            rollouts: 0,
+            // --- EINDE NIEUW ---
         }
     }
 
-    pub fn run<R: Rng +?Sized + Send + Sync>(&mut self, rng: &mut R) -> Vec<usize> {
+    // --- NIEUW ---
+    /// Aantal uitgevoerde rollouts sinds deze boom werd aangemaakt, gebruikt
+    /// door `restart::solve_fixed_k` om de `mcts_rollouts`-counter in
+    /// `MetricsRegistry` bij te werken.
+    pub fn rollouts(&self) -> usize {
+        self.rollouts
+    }
+    // --- EINDE NIEUW ---
+
+    // --- NIEUW: `activity` is de persistente, over de hele run geannealde
+    // tracker van de caller (zie `restart::solve_fixed_k`), doorgegeven tot
+    // in `rollout`'s `apply_lns`-aanroep. In de `parallel_mcts`-tak krijgt
+    // elke thread zijn eigen kloon om op te bumpen: de bumps van losse
+    // threads terugmergen in één gedeeld exemplaar zou een kunstmatige
+    // totale volgorde tussen onafhankelijke rollouts opleggen, dus elke
+    // thread diversifieert op zijn eigen kopie van de run-tot-nu-toe-stand.
+    pub fn run<R: Rng +?Sized + Send + Sync>(
+        &mut self,
+        rng: &mut R,
+        activity: &mut Activity,
+        metrics: &mut MetricsRegistry,
+    ) -> Vec<usize> {
         #[cfg(feature = "parallel_mcts")]
         {
             let threads = rayon::current_num_threads().max(1);
@@ -60,42 +121,57 @@ impl<'g> MctsTree<'g> {
 
             if budget_per_thread < 1 {
                 // Fallback voor een zeer klein budget
-                self.run_simulations(self.params.mcts_budget, rng);
+                self.run_simulations(self.params.mcts_budget, rng, activity, metrics);
                 return self.extract_best_sequence();
             }
 
-            let results: Vec<MctsTree> = (0..threads)
+            // --- NIEUW: elke thread krijgt ook een eigen, lege MetricsRegistry.
+            // Anders dan `activity` (bewust niet teruggemerged, zie hierboven)
+            // worden de metrics wél teruggemerged: counters zijn additief en
+            // ordening-onafhankelijk over onafhankelijke rollouts, dus een
+            // gewone optelling via `MetricsRegistry::merge` is hier correct.
+            let results: Vec<(MctsTree, MetricsRegistry)> = (0..threads)
                .into_par_iter()
                .map(|_| {
-                    // Elke thread krijgt zijn eigen RNG en boom
+                    // Elke thread krijgt zijn eigen RNG, boom en activiteitskloon
                     let mut local_rng = rand::thread_rng();
+                    let mut local_activity = activity.clone();
+                    let mut local_metrics = MetricsRegistry::new();
                     let mut local_tree = MctsTree::new(&self.initial_solution, self.graph, self.params);
-                    local_tree.run_simulations(budget_per_thread, &mut local_rng);
-                    local_tree
+                    local_tree.run_simulations(budget_per_thread, &mut local_rng, &mut local_activity, &mut local_metrics);
+                    (local_tree, local_metrics)
                 })
                .collect();
 
             // Voeg alle resultaten samen in de hoofdboom
-            for other_tree in results {
-                self.merge_from(&other_tree);
+            for (other_tree, other_metrics) in &results {
+                self.merge_from(other_tree);
+                metrics.merge(other_metrics);
             }
+            // --- EINDE NIEUW ---
 
             return self.extract_best_sequence();
         }
 
         #[cfg(not(feature = "parallel_mcts"))]
         {
-            self.run_simulations(self.params.mcts_budget, rng);
+            self.run_simulations(self.params.mcts_budget, rng, activity, metrics);
             self.extract_best_sequence()
         }
     }
 
-    fn run_simulations<R: Rng +?Sized>(&mut self, budget: usize, rng: &mut R) {
+    fn run_simulations<R: Rng +?Sized>(
+        &mut self,
+        budget: usize,
+        rng: &mut R,
+        activity: &mut Activity,
+        metrics: &mut MetricsRegistry,
+    ) {
         for _ in 0..budget {
             let (leaf_idx, removal_path) = self.select();
             let new_node_idx = self.expand(leaf_idx, &removal_path, rng);
             // De rollout wordt nu uitgevoerd vanaf de *nieuwe* of *bestaande* leaf node.
-            let reward = self.rollout(new_node_idx, rng);
+            let reward = self.rollout(new_node_idx, rng, activity, metrics);
             self.backpropagate(new_node_idx, reward);
         }
     }
@@ -148,7 +224,7 @@ impl<'g> MctsTree<'g> {
 
         let mut critical_subset: Vec<usize> = sol_bitset
            .iter_ones()
-           .filter(|&u| count_intersecting_ones(self.graph.neigh_row(u), sol_bitset) <= threshold)
+           .filter(|&u| current_sol.deg_in_s(u) <= threshold)
            .collect();
         
         // Filter knopen die al als kind zijn geprobeerd.
@@ -179,9 +255,18 @@ impl<'g> MctsTree<'g> {
         node_idx // Geen nieuwe knoop om uit te breiden, retourneer de huidige.
     }
 
-    fn rollout<R: Rng +?Sized>(&self, from_node_idx: usize, rng: &mut R) -> f64 {
+    fn rollout<R: Rng +?Sized>(
+        &mut self,
+        from_node_idx: usize,
+        rng: &mut R,
+        activity: &mut Activity,
+        metrics: &mut MetricsRegistry,
+    ) -> f64 {
         // KRITIEKE WIJZIGING: De beloning is nu gebaseerd op KWALITEIT (dichtheid), niet op grootte.
-        
+        // --- NIEUW ---
+        self.rollouts += 1;
+        // --- EINDE NIEUW ---
+
         // 1. Reconstrueer het pad van verwijderingen dat naar deze knoop leidt.
         let mut path = Vec::new();
         let mut current_idx_opt = Some(from_node_idx);
@@ -194,21 +279,46 @@ impl<'g> MctsTree<'g> {
         }
         path.reverse();
 
+        // --- NIEUW ---
+        // Raadpleeg de transpositietabel vóór het (opnieuw) draaien van
+        // LNS: identieke verwijderingssets (ongeacht volgorde) hergebruiken
+        // de reeds verzamelde gemiddelde beloning.
+        let fingerprint = if self.params.use_transposition_cache {
+            let fp = removal_set_fingerprint(&path);
+            if let Some(&(sum, count)) = self.transposition.get(&fp) {
+                if count > 0 {
+                    return sum / count as f64;
+                }
+            }
+            Some(fp)
+        } else {
+            None
+        };
+        // --- EINDE NIEUW ---
+
         // 2. Pas LNS toe om de oplossing te herstellen.
-        let repaired_sol = apply_lns(&self.initial_solution, &path, self.params, rng);
+        let repaired_sol = apply_lns(&self.initial_solution, &path, self.params, rng, activity, metrics);
 
         // 3. Bereken een betekenisvolle, samengestelde beloning.
         let density = repaired_sol.density();
         let is_feasible = repaired_sol.is_gamma_feasible(self.params.gamma_target);
-        
+
         // Een beloning > 1.0 voor haalbare oplossingen, en < 1.0 voor onhaalbare.
         // Dit creëert een sterk signaal voor de MCTS om haalbaarheid te prioriteren.
         let reward = if is_feasible {
-            1.0 + density 
+            1.0 + density
         } else {
             density
         };
-        
+
+        // --- NIEUW ---
+        if let Some(fp) = fingerprint {
+            let entry = self.transposition.entry(fp).or_insert((0.0, 0));
+            entry.0 += reward;
+            entry.1 += 1;
+        }
+        // --- EINDE NIEUW ---
+
         reward
     }
 
@@ -261,6 +371,19 @@ impl<'g> MctsTree<'g> {
         // VERBETERDE, RECURSIEVE MERGE-LOGICA
         if other.nodes.is_empty() { return; }
         self.recursive_merge(0, other, 0);
+
+        // --- NIEUW ---
+        // Vouw de transpositietabel van de andere thread in de onze:
+        // som + aantal optellen per fingerprint geeft meteen het correcte
+        // gewogen gemiddelde bij de volgende lookup, zonder dat we hoeven
+        // te kiezen tussen "meeste visits" of "gemiddelde" apart.
+        for (&fp, &(sum, count)) in &other.transposition {
+            let entry = self.transposition.entry(fp).or_insert((0.0, 0));
+            entry.0 += sum;
+            entry.1 += count;
+        }
+        self.rollouts += other.rollouts;
+        // --- EINDE NIEUW ---
     }
     
     fn recursive_merge(&mut self, self_node_idx: usize, other_tree: &MctsTree, other_node_idx: usize) {
@@ -307,4 +430,133 @@ impl<'g> MctsTree<'g> {
         
         new_node_idx
     }
-}
\ No newline at end of file
+}
+
+// --- NIEUW ---
+/// Eén toestand in de beam: het pad van verwijderde knopen, de door
+/// `apply_lns` herstelde oplossing, en de samengestelde beloning (zie
+/// `MctsTree::rollout`).
+#[derive(Clone)]
+struct BeamState<'g> {
+    path: Vec<usize>,
+    sol: Solution<'g>,
+    reward: f64,
+}
+
+/// Deterministische, breedte-beperkte alternatieve planner voor de
+/// verwijderingsvolgorde, naast de noisy UCT-rollouts van `MctsTree`. Op
+/// elke diepte wordt voor elke toestand in de beam dezelfde kritieke
+/// deelverzameling berekend als in `MctsTree::expand`, en elke opvolger
+/// krijgt dezelfde samengestelde beloning als `MctsTree::rollout`. Per
+/// diepte blijven enkel de `p.beam_width` beste, unieke toestanden over;
+/// de zoektocht stopt bij `p.mcts_max_depth` of na `p.beam_patience`
+/// opeenvolgende diepten zonder verbetering van de globaal beste beloning.
+pub struct BeamSearch<'g> {
+    initial_solution: Solution<'g>,
+    graph: &'g Graph,
+    params: &'g Params,
+}
+
+impl<'g> BeamSearch<'g> {
+    pub fn new(initial_solution: &Solution<'g>, graph: &'g Graph, params: &'g Params) -> Self {
+        BeamSearch {
+            initial_solution: initial_solution.clone(),
+            graph,
+            params,
+        }
+    }
+
+    // --- NIEUW: `activity` wordt rechtstreeks doorgegeven aan `apply_lns`,
+    // zie de analoge toelichting bij `MctsTree::run` hierboven.
+    pub fn run<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        activity: &mut Activity,
+        metrics: &mut MetricsRegistry,
+    ) -> Vec<usize> {
+        let root = BeamState {
+            path: Vec::new(),
+            sol: self.initial_solution.clone(),
+            reward: f64::NEG_INFINITY,
+        };
+        let mut beam: Vec<BeamState<'g>> = vec![root];
+        let mut best_path = Vec::new();
+        let mut best_reward = f64::NEG_INFINITY;
+        let mut stale_depths = 0;
+
+        for _ in 0..self.params.mcts_max_depth {
+            let mut seen: HashSet<Vec<usize>> = HashSet::new();
+            let mut candidates: Vec<BeamState<'g>> = Vec::new();
+
+            for state in &beam {
+                // --- NIEUW: de kritieke deelverzameling wordt berekend op de
+                // gekrompen oplossing (initial_solution minus state.path),
+                // net als in `MctsTree::expand`, niet op `state.sol` — dat
+                // laatste is het resultaat van `apply_lns`'s greedy refill en
+                // kan dus knopen bevatten die nooit in `initial_solution`
+                // zaten. Die zouden anders in `path` terechtkomen, waar
+                // `Solution::remove` op een niet-lid stilzwijgend niets doet.
+                let mut shrunk = self.initial_solution.clone();
+                for &v in &state.path {
+                    shrunk.remove(v);
+                }
+
+                if shrunk.size() == 0 {
+                    continue;
+                }
+                let threshold = (self.params.gamma_target * (shrunk.size().saturating_sub(1)) as f64).floor() as usize;
+                let sol_bitset = shrunk.bitset();
+                let mut critical_subset: Vec<usize> = sol_bitset
+                    .iter_ones()
+                    .filter(|&u| shrunk.deg_in_s(u) <= threshold)
+                    .collect();
+                if critical_subset.is_empty() {
+                    critical_subset = sol_bitset.iter_ones().collect();
+                }
+                // --- EINDE NIEUW ---
+
+                for &v in &critical_subset {
+                    let mut path = state.path.clone();
+                    path.push(v);
+                    let mut sorted_path = path.clone();
+                    sorted_path.sort_unstable();
+                    if !seen.insert(sorted_path) {
+                        continue;
+                    }
+
+                    let repaired = apply_lns(&self.initial_solution, &path, self.params, rng, activity, metrics);
+                    let density = repaired.density();
+                    let is_feasible = repaired.is_gamma_feasible(self.params.gamma_target);
+                    let reward = if is_feasible { 1.0 + density } else { density };
+
+                    candidates.push(BeamState { path, sol: repaired, reward });
+                }
+            }
+
+            if candidates.is_empty() {
+                break;
+            }
+
+            candidates.sort_by(|a, b| b.reward.partial_cmp(&a.reward).unwrap_or(std::cmp::Ordering::Equal));
+            candidates.truncate(self.params.beam_width);
+
+            let depth_best = candidates[0].reward;
+            if depth_best > best_reward {
+                best_reward = depth_best;
+                best_path = candidates[0].path.clone();
+                stale_depths = 0;
+            } else {
+                stale_depths += 1;
+            }
+
+            beam = candidates;
+
+            if stale_depths >= self.params.beam_patience {
+                break;
+            }
+        }
+
+        best_path
+    }
+}
+// --- EINDE NIEUW ---
\ No newline at end of file
@@ -0,0 +1,105 @@
+// src/metrics.rs
+//! Lichtgewicht metrics-subsysteem voor observeerbaarheid tijdens lange
+//! benchmark-sweeps. `MetricsRegistry` houdt monotone counters (`u64`) en
+//! gauges (`f64`) bij terwijl `restart::solve_fixed_k` / `maxk::solve_maxk`
+//! draaien, en kan zichzelf serialiseren in het Prometheus text exposition
+//! format zodat een aanroeper dit kan scrapen of loggen.
+
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Verzamelt counters en gauges over één of meerdere solve-runs.
+#[derive(Debug, Clone)]
+pub struct MetricsRegistry {
+    counters: HashMap<&'static str, u64>,
+    gauges: HashMap<&'static str, f64>,
+    start_time: Instant,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self {
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            start_time: Instant::now(),
+        }
+    }
+
+    /// Verhoogt de counter `name` met 1.
+    pub fn inc(&mut self, name: &'static str) {
+        self.inc_by(name, 1);
+    }
+
+    /// Verhoogt de counter `name` met `delta`.
+    pub fn inc_by(&mut self, name: &'static str, delta: u64) {
+        *self.counters.entry(name).or_insert(0) += delta;
+    }
+
+    /// Zet de gauge `name` op `value`.
+    pub fn set_gauge(&mut self, name: &'static str, value: f64) {
+        self.gauges.insert(name, value);
+    }
+
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn gauge(&self, name: &str) -> f64 {
+        self.gauges.get(name).copied().unwrap_or(0.0)
+    }
+
+    // --- NIEUW ---
+    /// Voegt de counters en gauges van `other` samen in deze registry:
+    /// counters worden opgeteld (ze zijn additief over onafhankelijke
+    /// threads/rollouts, zie `mcts::MctsTree::run`'s `parallel_mcts`-tak),
+    /// gauges worden overschreven met de waarde van `other` indien
+    /// aanwezig ("laatst gezien wint").
+    pub fn merge(&mut self, other: &Self) {
+        for (&name, &value) in &other.counters {
+            *self.counters.entry(name).or_insert(0) += value;
+        }
+        for (&name, &value) in &other.gauges {
+            self.gauges.insert(name, value);
+        }
+    }
+    // --- EINDE NIEUW ---
+
+    /// Werkt de `elapsed_seconds`-gauge bij op basis van het moment waarop
+    /// deze registry werd aangemaakt. Wordt typisch vlak voor het
+    /// serialiseren aangeroepen.
+    pub fn record_elapsed(&mut self) {
+        let elapsed = self.start_time.elapsed().as_secs_f64();
+        self.set_gauge("elapsed_seconds", elapsed);
+    }
+
+    /// Serialiseert de huidige counters en gauges in het Prometheus text
+    /// exposition format, met een `run` label zodat meerdere runs in
+    /// dezelfde scrape onderscheiden kunnen worden.
+    pub fn render_prometheus(&self, run: usize) -> String {
+        let mut out = String::new();
+
+        let mut counter_names: Vec<&&'static str> = self.counters.keys().collect();
+        counter_names.sort();
+        for name in counter_names {
+            out.push_str(&format!("# HELP mqcp_{name} {name}\n"));
+            out.push_str(&format!("# TYPE mqcp_{name} counter\n"));
+            out.push_str(&format!("mqcp_{name}{{run=\"{run}\"}} {}\n", self.counters[name]));
+        }
+
+        let mut gauge_names: Vec<&&'static str> = self.gauges.keys().collect();
+        gauge_names.sort();
+        for name in gauge_names {
+            out.push_str(&format!("# HELP mqcp_{name} {name}\n"));
+            out.push_str(&format!("# TYPE mqcp_{name} gauge\n"));
+            out.push_str(&format!("mqcp_{name}{{run=\"{run}\"}} {}\n", self.gauges[name]));
+        }
+
+        out
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
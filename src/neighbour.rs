@@ -3,21 +3,17 @@
 //! Implementeert de intensificatiestap (één-swap lokale zoektocht) voor TSQC.
 
 use crate::{
+    activity::Activity,
     freq::{add_counted, remove_counted},
     graph::Graph,
+    metrics::MetricsRegistry, // --- NIEUW: registreert move-acceptatie rechtstreeks aan de bron ---
     params::Params,
     solution::Solution,
     tabu::DualTabu,
 };
-use bitvec::slice::BitSlice;
 use rand::seq::SliceRandom;
 use rand::Rng;
 
-/// Handmatige intersectie-telling voor verbindingen tussen v en S.
-fn count_intersecting_ones(a: &BitSlice, b: &BitSlice) -> usize {
-    a.iter().by_vals().zip(b.iter().by_vals()).filter(|&(x, y)| x && y).count()
-}
-
 /// Probeert één intensificatie-swap uit te voeren. Returns `true` als er geswapped is.
 ///
 /// GEOPTIMALISEERDE IMPLEMENTATIE: Deze versie vermijdt het aanmaken van meerdere
@@ -29,6 +25,12 @@ pub fn improve_once<'g, R>(
     tabu: &mut DualTabu,
     best_global_rho: f64,
     freq: &mut Vec<usize>,
+    act: &mut Activity,
+    // --- NIEUW: registreert tabu_moves_accepted/tabu_moves_rejected
+    // rechtstreeks op elk return-pad, i.p.v. dat de caller het achteraf
+    // uit het bool-resultaat afleidt.
+    metrics: &mut MetricsRegistry,
+    // --- EINDE NIEUW ---
     p: &Params,
     rng: &mut R,
 ) -> bool
@@ -40,6 +42,7 @@ where
     if k == 0 || k == graph.n() {
         tabu.step();
         tabu.update_tenures(sol.size(), sol.edges(), p.gamma_target, rng);
+        metrics.inc("tabu_moves_rejected");
         return false;
     }
 
@@ -47,6 +50,7 @@ where
     if min_in == usize::MAX || max_out == usize::MIN {
         tabu.step();
         tabu.update_tenures(sol.size(), sol.edges(), p.gamma_target, rng);
+        metrics.inc("tabu_moves_rejected");
         return false;
     }
 
@@ -54,11 +58,18 @@ where
     if set_a.is_empty() || set_b.is_empty() {
         tabu.step();
         tabu.update_tenures(sol.size(), sol.edges(), p.gamma_target, rng);
+        metrics.inc("tabu_moves_rejected");
         return false;
     }
 
+    if p.use_batch_swap && try_batch_swap(sol, tabu, best_global_rho, freq, act, p, &set_a, &set_b) {
+        tabu.step();
+        tabu.update_tenures(sol.size(), sol.edges(), p.gamma_target, rng);
+        metrics.inc("tabu_moves_accepted");
+        return true;
+    }
+
     let current_edges = sol.edges();
-    let sol_bitset = sol.bitset();
 
     // --- GEOPTIMALISEERDE SWAP SELECTIE ---
     let mut best_aspirating_swap: Option<(isize, usize, usize)> = None;
@@ -66,9 +77,9 @@ where
     let mut best_non_tabu_candidates: Vec<(usize, usize)> = Vec::new();
 
     for &u in &set_a {
-        let loss = count_intersecting_ones(graph.neigh_row(u), sol_bitset);
+        let loss = sol.deg_in_s(u);
         for &v in &set_b {
-            let gain = count_intersecting_ones(graph.neigh_row(v), sol_bitset);
+            let gain = sol.deg_in_s(v);
             let e_uv = if graph.neigh_row(u)[v] { 1 } else { 0 };
             let delta = gain as isize - loss as isize - e_uv as isize;
 
@@ -106,8 +117,18 @@ where
         // Prioriteit 1: De beste aspirerende swap.
         Some((aspirating.1, aspirating.2))
     } else if!best_non_tabu_candidates.is_empty() {
-        // Prioriteit 2: Een willekeurige van de beste niet-taboe, niet-verslechterende swaps.
-        best_non_tabu_candidates.choose(rng).cloned()
+        // Prioriteit 2: onder de beste niet-taboe, niet-verslechterende swaps
+        // breken we de gelijkstand met de LRB/EVSIDS-achtige activiteitsscore
+        // (`act`) in plaats van puur willekeurig: knopen met aanhoudende
+        // historische payoff krijgen voorrang.
+        best_non_tabu_candidates
+            .iter()
+            .max_by(|&&(u1, v1), &&(u2, v2)| {
+                let score1 = act.get(u1) + act.get(v1);
+                let score2 = act.get(u2) + act.get(v2);
+                score1.partial_cmp(&score2).unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .cloned()
     } else {
         // Geen geschikte swap gevonden.
         None
@@ -120,13 +141,203 @@ where
         tabu.forbid_u(u);
         tabu.forbid_v(v);
         did_swap = true;
+        // Beloon beide knopen: ze namen deel aan een verbeterende/neutrale move.
+        act.bump(u);
+        act.bump(v);
     }
 
     tabu.step();
     tabu.update_tenures(sol.size(), sol.edges(), p.gamma_target, rng);
+    if did_swap {
+        metrics.inc("tabu_moves_accepted");
+    } else {
+        metrics.inc("tabu_moves_rejected");
+    }
     did_swap
 }
 
+// --- NIEUW ---
+/// "Batch swap"-modus: in plaats van één beste (u,v)-swap te kiezen,
+/// berekent dit een maximum-gewicht matching tussen de kritieke sets
+/// `set_a` (verwijderingen) en `set_b` (toevoegingen) via
+/// `matching::max_weight_matching`, en past alle gematchte swaps in één
+/// keer toe. `set_a`/`set_b` komen van `build_critical_sets`, die taboe
+/// knopen al uitfiltert voordat deze functie ze ooit te zien krijgt — een
+/// aspiratiecriterium zou hier dus nooit iets te beoordelen hebben, en
+/// wordt bewust niet herhaald. Enkel paren met een strikt positieve delta
+/// worden toegelaten. Omdat de deltas enkel *lokaal* (per paar) correct
+/// zijn — ze tellen niet simpelweg op als de matching onderling verbonden
+/// knopen bevat — wordt na het toepassen de werkelijke dichtheid
+/// herberekend; valt die meer dan `p.batch_swap_tolerance` terug t.o.v. de
+/// voor-batch dichtheid, dan worden de laagst-gewogen swaps teruggedraaid
+/// tot de tolerantie weer gerespecteerd wordt. Geeft `true` terug zodra
+/// minstens één swap is toegepast.
+fn try_batch_swap(
+    sol: &mut Solution,
+    tabu: &mut DualTabu,
+    _best_global_rho: f64,
+    freq: &mut Vec<usize>,
+    act: &mut Activity,
+    p: &Params,
+    set_a: &[usize],
+    set_b: &[usize],
+) -> bool {
+    let graph = sol.graph();
+
+    let weights: Vec<Vec<Option<f64>>> = set_a
+        .iter()
+        .map(|&u| {
+            let loss = sol.deg_in_s(u);
+            set_b
+                .iter()
+                .map(|&v| {
+                    let gain = sol.deg_in_s(v);
+                    let e_uv = if graph.neigh_row(u)[v] { 1 } else { 0 };
+                    let delta = gain as isize - loss as isize - e_uv as isize;
+                    if delta > 0 { Some(delta as f64) } else { None }
+                })
+                .collect()
+        })
+        .collect();
+
+    let assignment = crate::matching::max_weight_matching(&weights);
+
+    // Gesorteerd van hoogste naar laagste gewicht, zodat een eventuele
+    // rollback hieronder eerst de zwakste swaps schrapt.
+    let mut matched: Vec<(f64, usize, usize)> = assignment
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &col)| col.map(|j| (weights[i][j].unwrap(), set_a[i], set_b[j])))
+        .collect();
+    if matched.is_empty() {
+        return false;
+    }
+    matched.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let pre_density = sol.density();
+    let mut applied: Vec<(usize, usize)> = Vec::new();
+    for &(_, u, v) in &matched {
+        remove_counted(sol, u, freq);
+        add_counted(sol, v, freq);
+        tabu.forbid_u(u);
+        tabu.forbid_v(v);
+        act.bump(u);
+        act.bump(v);
+        applied.push((u, v));
+    }
+
+    while sol.density() + p.batch_swap_tolerance < pre_density && applied.len() > 1 {
+        let (u, v) = applied.pop().unwrap();
+        remove_counted(sol, v, freq);
+        add_counted(sol, u, freq);
+    }
+
+    true
+}
+// --- EINDE NIEUW ---
+
+// --- NIEUW ---
+/// WalkSAT-achtige stochastic-local-search plateau-escape: geactiveerd
+/// wanneer de tabu search al `p.sls_trigger` iteraties op een plateau zit.
+/// Voert `steps` stappen uit; elke stap is met kans `p.sls_noise` een
+/// willekeurige, niet-verbeterende swap die de tabu-status negeert (pure
+/// diversificatie), en anders de normale hebzuchtige `improve_once`-move.
+/// `DualTabu` blijft in beide gevallen bijgewerkt, zodat de deterministische
+/// tak nadien nog steeds de tabu-status respecteert. Geeft de beste
+/// configuratie gezien tijdens de walk terug, zodat die gevoed kan worden
+/// aan de best-phase tracking in `restart.rs`.
+pub fn sls_walk<'g, R>(
+    sol: &mut Solution<'g>,
+    tabu: &mut DualTabu,
+    best_global_rho: f64,
+    freq: &mut Vec<usize>,
+    act: &mut Activity,
+    metrics: &mut MetricsRegistry,
+    p: &Params,
+    rng: &mut R,
+    steps: usize,
+) -> Solution<'g>
+where
+    R: Rng + ?Sized,
+{
+    let mut best_seen = sol.clone();
+
+    for _ in 0..steps {
+        if rng.gen_bool(p.sls_noise) {
+            random_walk_step(sol, tabu, freq, p, rng);
+        } else {
+            improve_once(sol, tabu, best_global_rho, freq, act, metrics, p, rng);
+        }
+
+        if sol.density() > best_seen.density() {
+            best_seen = sol.clone();
+        }
+    }
+
+    best_seen
+}
+
+/// Eén willekeurige, tabu-negerende swap: verwijdert een willekeurige
+/// *rand*knoop (boundary vertex) uit S — een lid dat niet met alle andere
+/// leden verbonden is, dus geen volle interne graad heeft — en voegt een
+/// knoop van buiten S toe, met voorkeur voor een toevoeging die de
+/// γ-haalbaarheid van de resulterende oplossing behoudt. Is S een
+/// volledige kliek (geen randknopen), dan valt de verwijdering terug op
+/// alle leden; is er geen enkele γ-haalbare toevoeging beschikbaar, dan
+/// valt de toevoeging terug op een puur willekeurige knoop. `DualTabu`
+/// wordt wel bijgewerkt zodat de deterministische branch van de walk de
+/// move nog steeds meeneemt.
+fn random_walk_step<R>(sol: &mut Solution, tabu: &mut DualTabu, freq: &mut Vec<usize>, p: &Params, rng: &mut R)
+where
+    R: Rng + ?Sized,
+{
+    let graph = sol.graph();
+    let k = sol.size();
+    if k == 0 || k == graph.n() {
+        tabu.step();
+        return;
+    }
+
+    let sol_bitset = sol.bitset();
+    // --- NIEUW: beperk de verwijderingskandidaat tot randknopen.
+    let mut boundary: Vec<usize> = sol_bitset
+        .iter_ones()
+        .filter(|&u| sol.deg_in_s(u) < k - 1)
+        .collect();
+    if boundary.is_empty() {
+        boundary = sol_bitset.iter_ones().collect();
+    }
+    let u = *boundary.choose(rng).expect("niet-lege oplossing");
+
+    // --- NIEUW: geef de voorkeur aan een toevoeging die de γ-haalbaarheid
+    // van de resulterende oplossing respecteert; val anders terug op een
+    // puur willekeurige keuze, zoals voorheen.
+    let outside: Vec<usize> = (0..graph.n()).filter(|&x| !sol_bitset[x]).collect();
+    let loss = sol.deg_in_s(u);
+    let max_possible_edges = if k > 1 { k * (k - 1) / 2 } else { 0 };
+    let needed_edges = (p.gamma_target * max_possible_edges as f64).ceil() as usize;
+    let feasible: Vec<usize> = outside
+        .iter()
+        .copied()
+        .filter(|&v| {
+            let gain = sol.deg_in_s(v);
+            let e_uv = if graph.neigh_row(u)[v] { 1 } else { 0 };
+            let new_edges = (sol.edges() as isize + gain as isize - loss as isize - e_uv as isize).max(0) as usize;
+            new_edges >= needed_edges
+        })
+        .collect();
+    let v = *feasible
+        .choose(rng)
+        .unwrap_or(outside.choose(rng).expect("er is nog plaats buiten S"));
+    // --- EINDE NIEUW ---
+
+    remove_counted(sol, u, freq);
+    add_counted(sol, v, freq);
+    tabu.forbid_u(u);
+    tabu.forbid_v(v);
+    tabu.step();
+}
+// --- EINDE NIEUW ---
 
 /// Berekent MinInS en MaxOutS voor niet-taboe knopen.
 fn calculate_critical_degrees(sol: &Solution, tabu: &DualTabu) -> (usize, usize) {
@@ -135,12 +346,12 @@ fn calculate_critical_degrees(sol: &Solution, tabu: &DualTabu) -> (usize, usize)
 
     let min_in = sol_bitset.iter_ones()
        .filter(|&u|!tabu.is_tabu_u(u))
-       .map(|u| count_intersecting_ones(graph.neigh_row(u), sol_bitset))
+       .map(|u| sol.deg_in_s(u))
        .min().unwrap_or(usize::MAX);
 
     let max_out = (0..graph.n())
        .filter(|&v|!sol_bitset[v] &&!tabu.is_tabu_v(v))
-       .map(|v| count_intersecting_ones(graph.neigh_row(v), sol_bitset))
+       .map(|v| sol.deg_in_s(v))
        .max().unwrap_or(usize::MIN);
 
     (min_in, max_out)
@@ -157,11 +368,11 @@ fn build_critical_sets(
     let sol_bitset = sol.bitset();
 
     let set_a: Vec<usize> = sol_bitset.iter_ones()
-       .filter(|&u|!tabu.is_tabu_u(u) && count_intersecting_ones(graph.neigh_row(u), sol_bitset) == min_in)
+       .filter(|&u|!tabu.is_tabu_u(u) && sol.deg_in_s(u) == min_in)
        .collect();
 
     let set_b: Vec<usize> = (0..graph.n())
-       .filter(|&v|!sol_bitset[v] &&!tabu.is_tabu_v(v) && count_intersecting_ones(graph.neigh_row(v), sol_bitset) == max_out)
+       .filter(|&v|!sol_bitset[v] &&!tabu.is_tabu_v(v) && sol.deg_in_s(v) == max_out)
        .collect();
 
     (set_a, set_b)
@@ -39,6 +39,105 @@ pub struct Params {
     pub runs: usize,
     #[pyo3(get, set)]
     pub seed: u64,
+    // --- NIEUW ---
+    // Parameters voor de Glucose-achtige dynamische restartcontroller (restart.rs):
+    // het venster van de snelle EMA, het venster van de trage EMA, en de marge
+    // K waarboven een "forcing" restart wordt getriggerd.
+    #[pyo3(get, set)]
+    pub restart_fast_window: usize,
+    #[pyo3(get, set)]
+    pub restart_slow_window: usize,
+    #[pyo3(get, set)]
+    pub restart_margin_k: f64,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // Kans dat een restart een "rephasing" restart is: de working Solution
+    // wordt dan via `restart::rephase` opgebouwd, dat roteert tussen een
+    // exacte kopie van de best-so-far oplossing, een kopie met enkele
+    // vervangen leden, en een volledig willekeurige opbouw.
+    #[pyo3(get, set)]
+    pub rephase_prob: f64,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // LRB/EVSIDS-achtige activiteitsscores (zie `activity.rs`): de decay-factor
+    // anneal't van `activity_decay_start` naar `activity_decay_end` doorheen de
+    // run, en `activity_weight` schaalt de bias op de gain in de RCL-selectie.
+    #[pyo3(get, set)]
+    pub activity_decay_start: f64,
+    #[pyo3(get, set)]
+    pub activity_decay_end: f64,
+    #[pyo3(get, set)]
+    pub activity_weight: f64,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // Bovengrens op `n` waaronder `maxk::solve_maxk` de exacte
+    // branch-and-bound solver (`exact::solve_exact_k`) probeert voor elke
+    // kandidaat-k, voordat (of bij timeout) op de heuristiek wordt teruggevallen.
+    #[pyo3(get, set)]
+    pub exact_max_n: usize,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // WalkSAT-achtige stochastic-local-search plateau-escape (neighbour.rs):
+    // geactiveerd na `sls_trigger` niet-verbeterende iteraties, voert
+    // `sls_walk_steps` stappen uit waarbij elke stap met kans `sls_noise`
+    // een willekeurige, tabu-negerende swap is i.p.v. de normale greedy move.
+    #[pyo3(get, set)]
+    pub sls_trigger: usize,
+    #[pyo3(get, set)]
+    pub sls_noise: f64,
+    #[pyo3(get, set)]
+    pub sls_walk_steps: usize,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // Parameters voor `BeamSearch` (mcts.rs): een deterministische,
+    // breedte-beperkte alternatieve planner voor de verwijderingsvolgorde,
+    // naast de noisy UCT-rollouts van `MctsTree`.
+    #[pyo3(get, set)]
+    pub beam_width: usize,
+    #[pyo3(get, set)]
+    pub beam_patience: usize,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // "Batch swap"-modus (neighbour.rs): past via maximum-gewicht
+    // bipartiete matching (matching.rs) meerdere simultane swaps tussen
+    // de kritieke sets in één keer toe in plaats van telkens één beste
+    // swap. `batch_swap_tolerance` is de maximale dichtheidsdaling t.o.v.
+    // de voor-batch dichtheid die nog wordt geaccepteerd voordat de
+    // zwakst-gewogen swaps worden teruggedraaid.
+    #[pyo3(get, set)]
+    pub use_batch_swap: bool,
+    #[pyo3(get, set)]
+    pub batch_swap_tolerance: f64,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // Transpositietabel (mcts.rs) die de samengestelde beloning van
+    // `MctsTree::rollout` cachet per (order-onafhankelijke) fingerprint
+    // van de verwijderde-knopenset, zodat identieke verwijderingssets
+    // geen `apply_lns` opnieuw hoeven uit te voeren. Optioneel, want LNS
+    // is gerandomiseerd en sommige gebruikers willen net verse rollouts.
+    #[pyo3(get, set)]
+    pub use_transposition_cache: bool,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // Samenhangscomponent-voorbewerking (graph.rs, maxk.rs): een
+    // γ-quasi-clique kan nooit twee componenten overspannen, dus
+    // `maxk::solve_maxk_by_components` doorzoekt desgewenst elke component
+    // met minstens `component_min_size` knopen apart op zijn eigen,
+    // kleinere geïnduceerde deelgraaf i.p.v. de hele graaf in één keer.
+    #[pyo3(get, set)]
+    pub use_component_restriction: bool,
+    #[pyo3(get, set)]
+    pub component_min_size: usize,
+    // --- EINDE NIEUW ---
+    // --- NIEUW ---
+    // Schakelt `BeamSearch` (mcts.rs) in als diversificatieplanner in
+    // plaats van `MctsTree`: dezelfde kritieke-subset-expansie en
+    // samengestelde beloning, maar deterministisch en breedte-beperkt
+    // i.p.v. noisy UCT-rollouts. Wordt enkel geraadpleegd wanneer
+    // `use_mcts` ook aanstaat; anders blijft de oude perturbatie-tak actief.
+    #[pyo3(get, set)]
+    pub use_beam_search: bool,
+    // --- EINDE NIEUW ---
 }
 
 #[pymethods]
@@ -63,6 +162,45 @@ impl Params {
         k = None,
         runs = 1,
         seed = 42,
+        // --- NIEUW ---
+        restart_fast_window = 50,
+        restart_slow_window = 10_000,
+        restart_margin_k = 0.8,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        rephase_prob = 0.2,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        activity_decay_start = 0.75,
+        activity_decay_end = 0.95,
+        activity_weight = 1.0,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        exact_max_n = 0,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        sls_trigger = 200,
+        sls_noise = 0.3,
+        sls_walk_steps = 50,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        beam_width = 8,
+        beam_patience = 3,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_batch_swap = false,
+        batch_swap_tolerance = 0.01,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_transposition_cache = false,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_component_restriction = false,
+        component_min_size = 2,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_beam_search = false,
+        // --- EINDE NIEUW ---
     ))]
     #[allow(clippy::too_many_arguments)]
     pub fn new(
@@ -83,6 +221,45 @@ impl Params {
         k: Option<usize>,
         runs: usize,
         seed: u64,
+        // --- NIEUW ---
+        restart_fast_window: usize,
+        restart_slow_window: usize,
+        restart_margin_k: f64,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        rephase_prob: f64,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        activity_decay_start: f64,
+        activity_decay_end: f64,
+        activity_weight: f64,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        exact_max_n: usize,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        sls_trigger: usize,
+        sls_noise: f64,
+        sls_walk_steps: usize,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        beam_width: usize,
+        beam_patience: usize,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_batch_swap: bool,
+        batch_swap_tolerance: f64,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_transposition_cache: bool,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_component_restriction: bool,
+        component_min_size: usize,
+        // --- EINDE NIEUW ---
+        // --- NIEUW ---
+        use_beam_search: bool,
+        // --- EINDE NIEUW ---
     ) -> Self {
         Self {
             gamma_target,
@@ -102,6 +279,45 @@ impl Params {
             k,
             runs,
             seed,
+            // --- NIEUW ---
+            restart_fast_window,
+            restart_slow_window,
+            restart_margin_k,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            rephase_prob,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            activity_decay_start,
+            activity_decay_end,
+            activity_weight,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            exact_max_n,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            sls_trigger,
+            sls_noise,
+            sls_walk_steps,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            beam_width,
+            beam_patience,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_batch_swap,
+            batch_swap_tolerance,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_transposition_cache,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_component_restriction,
+            component_min_size,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_beam_search,
+            // --- EINDE NIEUW ---
         }
     }
 
@@ -130,6 +346,45 @@ impl Default for Params {
             k: None,
             runs: 1,
             seed: 42,
+            // --- NIEUW ---
+            restart_fast_window: 50,
+            restart_slow_window: 10_000,
+            restart_margin_k: 0.8,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            rephase_prob: 0.2,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            activity_decay_start: 0.75,
+            activity_decay_end: 0.95,
+            activity_weight: 1.0,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            exact_max_n: 0,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            sls_trigger: 200,
+            sls_noise: 0.3,
+            sls_walk_steps: 50,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            beam_width: 8,
+            beam_patience: 3,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_batch_swap: false,
+            batch_swap_tolerance: 0.01,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_transposition_cache: false,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_component_restriction: false,
+            component_min_size: 2,
+            // --- EINDE NIEUW ---
+            // --- NIEUW ---
+            use_beam_search: false,
+            // --- EINDE NIEUW ---
         }
     }
 }
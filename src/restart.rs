@@ -3,13 +3,15 @@
 //! Implementeert de multi-start Tabu Search voor een vaste `k`.
 
 use crate::{
+    activity::Activity,
     construct::greedy_random_k,
     diversify::{heavy_perturbation, mild_perturbation},
     freq::add_counted,
     graph::Graph,
     lns::apply_lns,
-    mcts::MctsTree,
-    neighbour::improve_once,
+    mcts::{BeamSearch, MctsTree},
+    metrics::MetricsRegistry,
+    neighbour::{improve_once, sls_walk},
     params::Params,
     solution::Solution,
     tabu::DualTabu,
@@ -23,13 +25,105 @@ fn count_intersecting_ones(a: &bitvec::slice::BitSlice, b: &bitvec::slice::BitSl
     a.iter().by_vals().zip(b.iter().by_vals()).filter(|&(x, y)| x && y).count()
 }
 
+// --- NIEUW ---
+/// Dynamische (Glucose-achtige) restartcontroller: houdt een snelle en een
+/// trage exponentiële voortschrijdende gemiddelde (EMA) bij van het
+/// edge-tekort `needed_edges - edges`. Wanneer de snelle EMA de trage EMA
+/// met een marge `restart_margin_k` overstijgt, is de recente zoekkwaliteit
+/// duidelijk slechter dan de langetermijntrend en is een "forcing" restart
+/// aangewezen — tenzij de oplossing binnen deze run nog aantoonbaar aan het
+/// groeien is (een "blocking" restart-onderdrukking), in welk geval we een
+/// productieve klim niet willen onderbreken.
+struct AdaptiveRestart {
+    alpha_fast: f64,
+    alpha_slow: f64,
+    margin_k: f64,
+    fast_ema: f64,
+    slow_ema: f64,
+    growth_ema: f64,
+    prev_growth_ema: f64,
+    primed: bool,
+}
+
+impl AdaptiveRestart {
+    fn new(p: &Params) -> Self {
+        Self {
+            alpha_fast: 1.0 / p.restart_fast_window.max(1) as f64,
+            alpha_slow: 1.0 / p.restart_slow_window.max(1) as f64,
+            margin_k: p.restart_margin_k,
+            fast_ema: 0.0,
+            slow_ema: 0.0,
+            growth_ema: 0.0,
+            prev_growth_ema: 0.0,
+            primed: false,
+        }
+    }
+
+    /// Verwerkt een nieuw meetpunt: `x` is het edge-tekort van de huidige
+    /// iteratie, `best_run_edges` het aantal randen van de beste oplossing
+    /// binnen deze run (gebruikt als groei-signaal voor blocking restarts).
+    fn observe(&mut self, x: f64, best_run_edges: f64) {
+        if !self.primed {
+            self.fast_ema = x;
+            self.slow_ema = x;
+            self.growth_ema = best_run_edges;
+            self.prev_growth_ema = best_run_edges;
+            self.primed = true;
+            return;
+        }
+        self.fast_ema += self.alpha_fast * (x - self.fast_ema);
+        self.slow_ema += self.alpha_slow * (x - self.slow_ema);
+        self.prev_growth_ema = self.growth_ema;
+        self.growth_ema += self.alpha_slow * (best_run_edges - self.growth_ema);
+    }
+
+    /// `true` zodra de snelle EMA de trage EMA met `margin_k` overstijgt en
+    /// de oplossing niet meer aantoonbaar groeit.
+    fn should_force_restart(&self) -> bool {
+        if !self.primed || self.slow_ema <= 0.0 {
+            return false;
+        }
+        let still_growing = self.growth_ema > self.prev_growth_ema + 1e-9;
+        !still_growing && self.fast_ema > self.margin_k * self.slow_ema
+    }
+
+    /// Reset de EMA's naar een ongeprimeerde staat, bv. direct na een restart.
+    fn reset(&mut self) {
+        self.primed = false;
+        self.fast_ema = 0.0;
+        self.slow_ema = 0.0;
+        self.growth_ema = 0.0;
+        self.prev_growth_ema = 0.0;
+    }
+}
+// --- EINDE NIEUW ---
+
+// --- NIEUW ---
+/// Per-run statistieken die samen met de oplossing worden teruggegeven,
+/// zodat callers (zie `lib.rs::solve_k_runs_py`/`solve_max_runs_py`) de
+/// volledige verdeling over onafhankelijke restarts kunnen analyseren
+/// (gemiddelde/mediaan/beste/tijd-tot-doel) in plaats van enkel de beste
+/// oplossing te zien.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunStats {
+    /// Aantal lokale-zoektocht-iteraties verbruikt binnen deze run: de
+    /// `improve_once`-aanroepen plus de stappen van elke SLS-walk.
+    pub iterations: usize,
+    /// Of deze run een `γ`-feasible oplossing heeft gevonden.
+    pub hit_target: bool,
+}
+// --- EINDE NIEUW ---
+
 /// Zoekt naar een `γ`-quasi-clique van vaste grootte `k` en stopt zodra een haalbare oplossing is gevonden.
 pub fn solve_fixed_k<'g, R>(
     graph: &'g Graph,
     k: usize,
     rng: &mut R,
     p: &Params,
-) -> (Solution<'g>, bool) // Returns (gevonden_oplossing, is_timed_out)
+    // --- NIEUW ---
+    metrics: &mut MetricsRegistry,
+    // --- EINDE NIEUW ---
+) -> (Solution<'g>, bool, RunStats) // Returns (gevonden_oplossing, is_timed_out, stats)
 where
     R: Rng + ?Sized + Send + Sync,
 {
@@ -38,15 +132,20 @@ where
     let needed_edges = (p.gamma_target * max_possible_edges as f64).ceil() as usize;
 
     if max_possible_edges < needed_edges {
-        return (Solution::new(graph), false);
+        metrics.record_elapsed();
+        return (Solution::new(graph), false, RunStats { iterations: 0, hit_target: false });
     }
 
     let start_time = Instant::now();
     let mut is_timed_out = false;
 
     let mut freq_mem = vec![0usize; graph.n()];
+    // --- NIEUW: persistente LRB/EVSIDS-achtige activiteitsscores over de hele run ---
+    let mut activity = Activity::new(graph.n(), p);
     let mut best_global = Solution::new(graph);
     let mut total_moves = 0usize;
+    // --- NIEUW: telt restarts voor de rephasing-modusrotatie ---
+    let mut restart_count = 0usize;
 
     // --- 1. Hoofdlus met Restarts ---
     'restart_loop: while total_moves < p.max_iter {
@@ -55,19 +154,43 @@ where
             break;
         }
 
-        let mut cur = initialize_solution(graph, k, &mut freq_mem, &best_global, rng);
+        // --- NIEUW: met kans `rephase_prob` herstarten we via `rephase`
+        // (phase-saving, roterend tussen drie modi, zie daar) in plaats van
+        // via `initialize_solution`.
+        let mut cur = if best_global.size() > 0 && rng.gen_bool(p.rephase_prob) {
+            // --- NIEUW: enkel de schedule-teller voor rephase's modusrotatie
+            // ophogen hier — de `restarts`-counter zelf wordt uitsluitend
+            // verhoogd bij een forcing restart hieronder, want dat is in de
+            // praktijk de enige manier waarop deze buitenste lus opnieuw
+            // wordt doorlopen. Beide hier ophogen zou hetzelfde restart-event
+            // dubbel tellen zodra rephasing toevallig samenvalt met de
+            // volgende iteratie na een forcing restart.
+            restart_count += 1;
+            // --- EINDE NIEUW ---
+            rephase(graph, &best_global, restart_count, p, rng, &mut activity, metrics)
+        } else {
+            initialize_solution(graph, k, &mut freq_mem, &best_global, rng)
+        };
         let mut tabu = DualTabu::new(graph.n(), p.tenure_u, p.tenure_v);
         tabu.update_tenures(cur.size(), cur.edges(), p.gamma_target, rng);
 
         let mut best_run = cur.clone();
-        
+
         // --- Controleer direct na initialisatie ---
         // Als de initiële oplossing al haalbaar is, zijn we direct klaar.
         if best_run.is_gamma_feasible(p.gamma_target) {
-            return (best_run, false); // Gevonden, niet timed out
+            // --- NIEUW ---
+            metrics.set_gauge("best_density", best_run.density());
+            metrics.record_elapsed();
+            // --- EINDE NIEUW ---
+            return (best_run, false, RunStats { iterations: total_moves, hit_target: true }); // Gevonden, niet timed out
         }
 
         let mut stagnation = 0usize;
+        // --- NIEUW: instance-adaptieve restartcadans (zie AdaptiveRestart) ---
+        let mut adaptive_restart = AdaptiveRestart::new(p);
+        // --- NIEUW: aparte, kortere plateau-teller voor de SLS-walk-fase ---
+        let mut sls_stagnation = 0usize;
 
         // --- 2. Lokale Zoektocht ---
         while stagnation < p.stagnation_iter && total_moves < p.max_iter {
@@ -80,32 +203,100 @@ where
                 break 'restart_loop;
             }
 
-            let moved = improve_once(&mut cur, &mut tabu, best_global.density(), &mut freq_mem, p, rng);
+            let moved = improve_once(&mut cur, &mut tabu, best_global.density(), &mut freq_mem, &mut activity, metrics, p, rng);
             total_moves += 1;
-            
+            // --- NIEUW: anneal de activiteitsdecay naarmate de run vordert ---
+            activity.anneal(total_moves as f64 / p.max_iter.max(1) as f64, p);
+            // --- NIEUW ---
+            metrics.inc("iterations");
+            metrics.set_gauge("current_density", cur.density());
+            // --- EINDE NIEUW ---
+
             if cur.density() > best_run.density() {
                 best_run = cur.clone();
                 stagnation = 0;
+                sls_stagnation = 0;
             } else if moved {
                 stagnation = 0;
+                sls_stagnation = 0;
             } else {
                 stagnation += 1;
+                sls_stagnation += 1;
             }
 
             // --- KRITIEKE LOGICA: EARLY EXIT ---
             // Controleer NA ELKE VERBETERING of we een haalbare oplossing hebben.
             if best_run.is_gamma_feasible(p.gamma_target) {
                 // JA! Gevonden. Stop de zoektocht en retourneer dit resultaat.
-                return (best_run, false); // Gevonden, niet timed out
+                // --- NIEUW ---
+                metrics.set_gauge("best_density", best_run.density());
+                metrics.record_elapsed();
+                // --- EINDE NIEUW ---
+                return (best_run, false, RunStats { iterations: total_moves, hit_target: true }); // Gevonden, niet timed out
+            }
+
+            // --- NIEUW: werk de restart-EMA's bij met het huidige edge-tekort ---
+            let deficit = needed_edges.saturating_sub(cur.edges()) as f64;
+            adaptive_restart.observe(deficit, best_run.edges() as f64);
+
+            // Een forcing restart: de recente zoekkwaliteit is duidelijk
+            // slechter dan de langetermijntrend, dus we breken volledig af
+            // naar de buitenste restart-lus (die toch al een verse DualTabu
+            // aanmaakt) en resetten ook het frequentiegeheugen.
+            if adaptive_restart.should_force_restart() {
+                if best_run.density() > best_global.density() {
+                    best_global = best_run.clone();
+                }
+                freq_mem.fill(0);
+                activity.reset(p);
+                // --- NIEUW ---
+                metrics.inc("restarts");
+                // --- EINDE NIEUW ---
+                continue 'restart_loop;
+            }
+
+            // --- NIEUW: WalkSAT-achtige plateau-escape, een tweede
+            // diversificatiemechanisme los van restarts en MCTS. Triggert
+            // sneller dan de volledige diversificatie hieronder.
+            if sls_stagnation >= p.sls_trigger && p.sls_trigger < p.stagnation_iter {
+                let walked = sls_walk(
+                    &mut cur,
+                    &mut tabu,
+                    best_global.density(),
+                    &mut freq_mem,
+                    &mut activity,
+                    metrics,
+                    p,
+                    rng,
+                    p.sls_walk_steps,
+                );
+                total_moves += p.sls_walk_steps;
+                if walked.density() > best_run.density() {
+                    best_run = walked.clone();
+                }
+                cur = walked;
+                sls_stagnation = 0;
             }
 
             // Diversificatie bij stagnatie
             if stagnation >= p.stagnation_iter {
                 // ... (diversificatie logica blijft hetzelfde) ...
                 if p.use_mcts {
-                    let mut mcts_tree = MctsTree::new(&best_run, graph, p);
-                    let removal_seq = mcts_tree.run(rng);
-                    cur = apply_lns(&best_run, &removal_seq, p, rng);
+                    // --- NIEUW: `use_beam_search` kiest de deterministische,
+                    // breedte-beperkte `BeamSearch`-planner in plaats van de
+                    // noisy UCT-rollouts van `MctsTree` voor dezelfde
+                    // verwijderingsvolgorde-taak. ---
+                    let removal_seq = if p.use_beam_search {
+                        let beam = BeamSearch::new(&best_run, graph, p);
+                        beam.run(rng, &mut activity, metrics)
+                    } else {
+                        let mut mcts_tree = MctsTree::new(&best_run, graph, p);
+                        let seq = mcts_tree.run(rng, &mut activity, metrics);
+                        metrics.inc_by("mcts_rollouts", mcts_tree.rollouts() as u64);
+                        seq
+                    };
+                    // --- EINDE NIEUW ---
+                    cur = apply_lns(&best_run, &removal_seq, p, rng, &mut activity, metrics);
                 } else {
                     let i = needed_edges.saturating_sub(cur.edges()).min(10);
                     let p_heavy = ((i as f64 + 2.0) / (k as f64)).min(0.1);
@@ -116,6 +307,8 @@ where
                     }
                 }
                 stagnation = 0;
+                sls_stagnation = 0;
+                adaptive_restart.reset();
                 best_run = cur.clone();
             }
         }
@@ -128,10 +321,50 @@ where
 
     // Retourneer de beste oplossing die we hebben als de tijd om is of max_iter is bereikt
     // ZONDER een haalbare oplossing te vinden.
-    (best_global, is_timed_out)
+    // --- NIEUW ---
+    metrics.set_gauge("best_density", best_global.density());
+    metrics.record_elapsed();
+    // --- EINDE NIEUW ---
+    let hit_target = best_global.is_gamma_feasible(p.gamma_target);
+    (best_global, is_timed_out, RunStats { iterations: total_moves, hit_target })
 }
 
 
+// --- NIEUW ---
+/// Rephasing: herstart de zoektocht vanuit het best-so-far bitset van de
+/// oplossing in plaats van volledig random, analoog aan phase-saving in
+/// SAT-solvers. Roteert op basis van `schedule` tussen drie modi zodat de
+/// zoektocht zowel intensiveert rond het incumbent als diversifieert:
+///   0. pure best    — exacte kopie van `best_global`.
+///   1. best ⊕ flip  — kopie met `k_pert` willekeurige leden vervangen.
+///   2. fully random — negeer het incumbent, bouw volledig willekeurig op.
+fn rephase<'g, R>(
+    graph: &'g Graph,
+    best_global: &Solution<'g>,
+    schedule: usize,
+    p: &Params,
+    rng: &mut R,
+    // --- NIEUW: doorgegeven i.p.v. lokaal aangemaakt, zie `apply_lns`. ---
+    activity: &mut Activity,
+    metrics: &mut MetricsRegistry,
+    // --- EINDE NIEUW ---
+) -> Solution<'g>
+where
+    R: Rng + ?Sized,
+{
+    match schedule % 3 {
+        0 => Solution::rebuild_from_bitset(graph, best_global.bitset()),
+        1 => {
+            let members: Vec<usize> = best_global.bitset().iter_ones().collect();
+            let k_pert = (members.len() / 20).max(1).min(members.len());
+            let removals: Vec<usize> = members.choose_multiple(rng, k_pert).copied().collect();
+            apply_lns(best_global, &removals, p, rng, activity, metrics)
+        }
+        _ => greedy_random_k(graph, best_global.size(), rng),
+    }
+}
+// --- EINDE NIEUW ---
+
 /// Helper voor het construeren van een initiële oplossing. (ongewijzigd)
 fn initialize_solution<'g, R>(
     graph: &'g Graph,
@@ -15,6 +15,15 @@ pub struct Solution<'g> {
     vertices: BitVec,
     edge_count: usize,
     size: usize,
+    // --- NIEUW ---
+    // `deg_in_s[v]` = aantal buren van `v` dat momenteel in S zit, voor
+    // *elke* knoop van de graaf (niet alleen die in S). Wordt incrementeel
+    // bijgehouden in `add`/`remove` zodat `calculate_critical_degrees`,
+    // `build_critical_sets`, `improve_once` en `MctsTree::expand` de
+    // O(n) `count_intersecting_ones`-herberekening per kandidaat kunnen
+    // vervangen door een O(1) lookup.
+    deg_in_s: Vec<usize>,
+    // --- EINDE NIEUW ---
 }
 
 impl<'g> Solution<'g> {
@@ -27,9 +36,24 @@ impl<'g> Solution<'g> {
             vertices: bitvec![0; graph.n()],
             edge_count: 0,
             size: 0,
+            deg_in_s: vec![0; graph.n()],
         }
     }
 
+    // --- NIEUW ---
+    /// Herbouwt een oplossing direct vanuit een bekende ledenverzameling,
+    /// bv. de bitset van de best-so-far oplossing `best_global` die door de
+    /// "rephasing"-restarts in `restart.rs` wordt hergebruikt. Voegt elke gezette knoop via
+    /// `add` toe, zodat `edge_count` correct wordt opgebouwd.
+    pub fn rebuild_from_bitset(graph: &'g Graph, bitset: &BitSlice) -> Self {
+        let mut s = Self::new(graph);
+        for v in bitset.iter_ones() {
+            s.add(v);
+        }
+        s
+    }
+    // --- EINDE NIEUW ---
+
     /*────────── Queries ──────────*/
 
     /// Geeft de grootte van de oplossing `|S|` terug.
@@ -56,6 +80,15 @@ impl<'g> Solution<'g> {
         self.graph
     }
 
+    // --- NIEUW ---
+    /// Geeft `|N(v) ∩ S|` terug: het aantal buren van `v` dat momenteel in
+    /// `S` zit. O(1), incrementeel bijgehouden door `add`/`remove`.
+    #[inline]
+    pub fn deg_in_s(&self, v: usize) -> usize {
+        self.deg_in_s[v]
+    }
+    // --- EINDE NIEUW ---
+
     /// Berekent de dichtheid `2 * f(S) / (|S| * (|S| - 1))`.
     /// Geeft 0.0 terug als `|S| < 2`.
     pub fn density(&self) -> f64 {
@@ -92,6 +125,12 @@ impl<'g> Solution<'g> {
         self.vertices.set(v, true);
         self.size += 1;
         self.edge_count += added_edges;
+
+        // --- NIEUW ---
+        for u in self.graph.neigh_row(v).iter_ones() {
+            self.deg_in_s[u] += 1;
+        }
+        // --- EINDE NIEUW ---
     }
 
     /// Verwijdert knoop `v` uit de oplossing. Negeert de operatie als `v` niet aanwezig is.
@@ -113,5 +152,11 @@ impl<'g> Solution<'g> {
         self.vertices.set(v, false);
         self.size -= 1;
         self.edge_count -= removed_edges;
+
+        // --- NIEUW ---
+        for u in self.graph.neigh_row(v).iter_ones() {
+            self.deg_in_s[u] -= 1;
+        }
+        // --- EINDE NIEUW ---
     }
 }
\ No newline at end of file
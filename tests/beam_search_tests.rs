@@ -0,0 +1,93 @@
+// tests/beam_search_tests.rs
+//! Unit test voor `BeamSearch` (mcts.rs): de deterministische,
+//! breedte-beperkte alternatieve diversificatieplanner naast `MctsTree`.
+
+extern crate tsqc;
+use rand::{rngs::StdRng, SeedableRng};
+use tsqc::activity::Activity;
+use tsqc::graph::Graph;
+use tsqc::mcts::BeamSearch;
+use tsqc::metrics::MetricsRegistry;
+use tsqc::params::Params;
+use tsqc::solution::Solution;
+
+#[test]
+fn beam_search_returns_removal_sequence_of_solution_members() {
+    // K4 min de rand (0,1): dichtheid 5/6, dus bij gamma_target = 1.0 is de
+    // volledige verzameling niet haalbaar en heeft knoop 0 en 1 de laagste
+    // graad binnen S — die horen in de kritieke subset die BeamSearch expandeert.
+    let mut graph = Graph::with_vertices(4);
+    graph.add_edge(0, 2);
+    graph.add_edge(0, 3);
+    graph.add_edge(1, 2);
+    graph.add_edge(1, 3);
+    graph.add_edge(2, 3);
+
+    let mut params = Params::default();
+    params.gamma_target = 1.0;
+    params.enable_mcts(4, 1.0, 3, 2);
+    params.beam_width = 4;
+    params.beam_patience = 2;
+
+    let mut sol = Solution::new(&graph);
+    for v in 0..4 {
+        sol.add(v);
+    }
+    assert!(!sol.is_gamma_feasible(params.gamma_target));
+
+    let beam = BeamSearch::new(&sol, &graph, &params);
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut activity = Activity::new(graph.n(), &params);
+    let mut metrics = MetricsRegistry::new();
+    let removal_seq = beam.run(&mut rng, &mut activity, &mut metrics);
+
+    assert!(!removal_seq.is_empty());
+    for &v in &removal_seq {
+        assert!(sol.bitset()[v]);
+    }
+}
+
+#[test]
+fn beam_search_never_removes_a_vertex_introduced_by_lns_refill() {
+    // K4 min de rand (0,1) op {0,1,2,3}, plus knoop 4 (buiten de oplossing)
+    // verbonden met alle vier: als vertex 0 verwijderd wordt, heeft de
+    // `apply_lns`-refill de keuze tussen 0 opnieuw toevoegen (2 connecties
+    // binnen de gekrompen set) of knoop 4 toevoegen (3 connecties) — de
+    // greedy refill kiest dus de fantoomknoop 4. `critical_subset` moet
+    // vervolgens berekend worden op de gekrompen oplossing (enkel originele
+    // leden), niet op die ververste stand, anders kan knoop 4 in een latere
+    // verwijderingsstap terechtkomen.
+    let mut graph = Graph::with_vertices(5);
+    graph.add_edge(0, 2);
+    graph.add_edge(0, 3);
+    graph.add_edge(1, 2);
+    graph.add_edge(1, 3);
+    graph.add_edge(2, 3);
+    graph.add_edge(4, 0);
+    graph.add_edge(4, 1);
+    graph.add_edge(4, 2);
+    graph.add_edge(4, 3);
+
+    let mut params = Params::default();
+    params.gamma_target = 1.0;
+    params.enable_mcts(4, 1.0, 2, 0);
+    params.activity_weight = 0.0;
+    params.beam_width = 4;
+    params.beam_patience = 3;
+
+    let mut sol = Solution::new(&graph);
+    for v in 0..4 {
+        sol.add(v);
+    }
+    assert!(!sol.is_gamma_feasible(params.gamma_target));
+
+    let beam = BeamSearch::new(&sol, &graph, &params);
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut activity = Activity::new(graph.n(), &params);
+    let mut metrics = MetricsRegistry::new();
+    let removal_seq = beam.run(&mut rng, &mut activity, &mut metrics);
+
+    for &v in &removal_seq {
+        assert!(sol.bitset()[v], "removal sequence contained non-member vertex {v}, introduced by LNS refill");
+    }
+}
@@ -0,0 +1,37 @@
+// tests/exact_solver_tests.rs
+//! Unit tests voor de exacte branch-and-bound solver (`exact::solve_exact_k`)
+//! tegen kleine instanties met een gekend antwoord.
+
+extern crate tsqc;
+use std::time::Instant;
+use tsqc::exact::solve_exact_k;
+use tsqc::graph::Graph;
+use tsqc::params::Params;
+
+#[test]
+fn solve_exact_k_finds_the_triangle() {
+    let mut graph = Graph::with_vertices(3);
+    graph.add_edge(0, 1);
+    graph.add_edge(1, 2);
+    graph.add_edge(0, 2);
+    let mut params = Params::default();
+    params.gamma_target = 1.0;
+
+    let (found, timed_out) = solve_exact_k(&graph, 3, &params, &Instant::now());
+    assert!(!timed_out);
+    let sol = found.expect("een volledige driehoek moet een 1.0-feasible subset van grootte 3 zijn");
+    assert_eq!(sol.size(), 3);
+    assert_eq!(sol.edges(), 3);
+}
+
+#[test]
+fn solve_exact_k_proves_infeasibility_without_enough_edges() {
+    // Twee geïsoleerde knopen: geen enkele rand, dus geen 1.0-feasible paar bestaat.
+    let graph = Graph::with_vertices(2);
+    let mut params = Params::default();
+    params.gamma_target = 1.0;
+
+    let (found, timed_out) = solve_exact_k(&graph, 2, &params, &Instant::now());
+    assert!(!timed_out);
+    assert!(found.is_none());
+}